@@ -0,0 +1,264 @@
+use criterion::{BatchSize, BenchmarkId, Criterion, Throughput, criterion_group, criterion_main};
+use fastxfix::{
+    CommonRaw, CommonStr, CommonStrMut, common_prefix_and_suffix_pair, common_prefix_endpoints,
+};
+use rayon::prelude::*;
+use std::hint::black_box;
+use std::sync::Arc;
+
+const FEW: usize = 1 << 4;
+const MANY: usize = 1 << 18;
+const SHORT: &str = "short_";
+const LONG: &str =
+    "this is a considerably longer shared region, long enough to span several chunks";
+const MULTIBYTE: &str = "ünïcödé_wörds_😀_";
+
+/// Builds `count` strings sharing `common` as a prefix (or suffix), with the
+/// varying part derived from the index so every run sees identical data.
+fn strings(common: &str, count: usize, prefix: bool) -> Vec<String> {
+    (0..count)
+        .map(|i| match prefix {
+            true => format!("{common}{i:016x}"),
+            false => format!("{i:016x}{common}"),
+        })
+        .collect()
+}
+
+fn str_prefix(c: &mut Criterion) {
+    let mut group = c.benchmark_group("str_prefix");
+    for (name, common) in [("short", SHORT), ("long", LONG), ("multibyte", MULTIBYTE)] {
+        for count in [FEW, MANY] {
+            let input = strings(common, count, true);
+            group.bench_with_input(BenchmarkId::new(name, count), &input, |b, input| {
+                b.iter(|| black_box(input).common_prefix_ref())
+            });
+        }
+    }
+    group.finish();
+}
+
+fn str_suffix(c: &mut Criterion) {
+    let mut group = c.benchmark_group("str_suffix");
+    for (name, common) in [("short", SHORT), ("long", LONG), ("multibyte", MULTIBYTE)] {
+        for count in [FEW, MANY] {
+            let input = strings(common, count, false);
+            group.bench_with_input(BenchmarkId::new(name, count), &input, |b, input| {
+                b.iter(|| black_box(input).common_suffix_ref())
+            });
+        }
+    }
+    group.finish();
+}
+
+fn raw(c: &mut Criterion) {
+    let mut group = c.benchmark_group("raw_u8");
+    for count in [FEW, MANY] {
+        let prefixed: Vec<Vec<u8>> = strings(LONG, count, true)
+            .into_iter()
+            .map(String::into_bytes)
+            .collect();
+        group.bench_with_input(BenchmarkId::new("prefix", count), &prefixed, |b, input| {
+            b.iter(|| black_box(input).common_prefix_raw_ref())
+        });
+        let suffixed: Vec<Vec<u8>> = strings(LONG, count, false)
+            .into_iter()
+            .map(String::into_bytes)
+            .collect();
+        group.bench_with_input(BenchmarkId::new("suffix", count), &suffixed, |b, input| {
+            b.iter(|| black_box(input).common_suffix_raw_ref())
+        });
+    }
+    group.finish();
+}
+
+fn worst_case(c: &mut Criterion) {
+    let mut group = c.benchmark_group("no_common");
+    // Only the last string breaks the common prefix, so nothing can be skipped early.
+    let mut input = strings(LONG, MANY, true);
+    input.last_mut().unwrap().insert(0, '!');
+    group.bench_function("last_differs", |b| {
+        b.iter(|| black_box(&input).common_prefix_ref())
+    });
+    // Only the first two strings share nothing, so everything after them can be skipped.
+    let mut input = strings(LONG, MANY * 4, true);
+    input[1].insert(0, '!');
+    group.bench_function("second_differs", |b| {
+        b.iter(|| black_box(&input).common_prefix_ref())
+    });
+    group.finish();
+}
+
+fn duplicates(c: &mut Criterion) {
+    let mut group = c.benchmark_group("duplicates");
+    // Every tenth string is distinct, the rest all share one allocation.
+    // The distinct ones extend the shared string so the result stays the same.
+    let shared: Arc<str> = LONG.repeat(64).into();
+    let input: Vec<Arc<str>> = (0..MANY)
+        .map(|i| match i % 10 {
+            9 => format!("{shared}{i:016x}").into(),
+            _ => Arc::clone(&shared),
+        })
+        .collect();
+    group.bench_function("prefix", |b| {
+        b.iter(|| black_box(&input).common_prefix_ref())
+    });
+    group.bench_function("suffix", |b| {
+        b.iter(|| black_box(&input).common_suffix_ref())
+    });
+    group.finish();
+}
+
+fn identical(c: &mut Criterion) {
+    let mut group = c.benchmark_group("identical");
+    // Equal strings, but every one of them in its own allocation.
+    let long = LONG.repeat(64);
+    for count in [FEW, MANY] {
+        let input = vec![long.clone(); count];
+        group.bench_with_input(BenchmarkId::new("prefix", count), &input, |b, input| {
+            b.iter(|| black_box(input).common_prefix_ref())
+        });
+        group.bench_with_input(BenchmarkId::new("suffix", count), &input, |b, input| {
+            b.iter(|| black_box(input).common_suffix_ref())
+        });
+    }
+    group.finish();
+}
+
+fn pair(c: &mut Criterion) {
+    let mut group = c.benchmark_group("pair");
+    // Below and above the length where prefix and suffix get found in one loop.
+    for (name, common) in [("short", SHORT), ("long", LONG)] {
+        let a = format!("{common}a{common}");
+        let b = format!("{common}bb{common}");
+        group.bench_function(BenchmarkId::new("fused", name), |bench| {
+            bench.iter(|| common_prefix_and_suffix_pair(black_box(&a), black_box(&b)))
+        });
+        let input = [a.clone(), b.clone()];
+        group.bench_function(BenchmarkId::new("collection", name), |bench| {
+            bench.iter(|| {
+                let input = black_box(&input);
+                (input.common_prefix_ref(), input.common_suffix_ref())
+            })
+        });
+    }
+    // Two 1KB strings, going through the collection methods and the pairwise function.
+    let shared = &LONG.repeat(16)[..1 << 10];
+    let (a, b) = (format!("{shared}a"), format!("{shared}b"));
+    let input = [a.clone(), b.clone()];
+    group.bench_function("collection_1kb", |bench| {
+        bench.iter(|| black_box(&input).common_prefix_ref())
+    });
+    group.bench_function("endpoints_1kb", |bench| {
+        bench.iter(|| common_prefix_endpoints(black_box(&a), black_box(&b)))
+    });
+    group.finish();
+}
+
+fn hierarchical(c: &mut Criterion) {
+    let mut group = c.benchmark_group("hierarchical");
+    // Every path lies beneath the root, which is also part of the collection.
+    let root = format!("/{LONG}/");
+    let mut input: Vec<String> = (0..MANY).map(|i| format!("{root}{i:016x}")).collect();
+    input.insert(MANY / 2, root);
+    group.bench_function("full", |b| b.iter(|| black_box(&input).common_prefix_ref()));
+    group.bench_function("shortest_first", |b| {
+        b.iter(|| black_box(&input).common_prefix_shortest_first())
+    });
+    group.finish();
+}
+
+fn dedup(c: &mut Criterion) {
+    let mut group = c.benchmark_group("dedup");
+    let long = LONG.repeat(4);
+    for unique in [100, MANY] {
+        let distinct = strings(&long, unique, true);
+        let input: Vec<String> = (0..MANY).map(|i| distinct[i % unique].clone()).collect();
+        group.bench_with_input(BenchmarkId::new("full", unique), &input, |b, input| {
+            b.iter(|| black_box(input).common_prefix())
+        });
+        group.bench_with_input(BenchmarkId::new("dedup", unique), &input, |b, input| {
+            b.iter(|| black_box(input).common_prefix_dedup())
+        });
+    }
+    group.finish();
+}
+
+fn strip(c: &mut Criterion) {
+    let mut group = c.benchmark_group("strip");
+    // Stripping mutates every string, so each iteration needs a fresh copy.
+    // Compare runs with different `RAYON_NUM_THREADS` to see how it scales.
+    let count = MANY * 4;
+    group.throughput(Throughput::Elements(count as u64));
+    group.sample_size(10);
+    let prefixed = strings(LONG, count, true);
+    group.bench_function("prefix", |b| {
+        b.iter_batched(
+            || prefixed.clone(),
+            |mut input| input.strip_common_prefix(),
+            BatchSize::LargeInput,
+        )
+    });
+    let suffixed = strings(LONG, count, false);
+    group.bench_function("suffix", |b| {
+        b.iter_batched(
+            || suffixed.clone(),
+            |mut input| input.strip_common_suffix(),
+            BatchSize::LargeInput,
+        )
+    });
+    group.finish();
+}
+
+fn chunked(c: &mut Criterion) {
+    let mut group = c.benchmark_group("chunked");
+    // How much sequential work each rayon job gets, against letting rayon decide.
+    for (name, common) in [("short", SHORT), ("long", LONG)] {
+        let input = strings(common, MANY, true);
+        group.bench_function(BenchmarkId::new("adaptive", name), |b| {
+            b.iter(|| black_box(&input).common_prefix_ref())
+        });
+        for chunk in [256, 4096] {
+            let id = BenchmarkId::new(format!("min_len_{chunk}"), name);
+            group.bench_function(id, |b| {
+                b.iter(|| black_box(&input).common_prefix_chunked(chunk))
+            });
+        }
+    }
+    group.finish();
+}
+
+fn starts_with(c: &mut Criterion) {
+    let mut group = c.benchmark_group("starts_with");
+    let input = strings(LONG, MANY, true);
+    group.bench_function("all_start_with", |b| {
+        b.iter(|| black_box(&input).all_start_with(LONG))
+    });
+    group.bench_function("std", |b| {
+        b.iter(|| black_box(&input).par_iter().all(|s| s.starts_with(LONG)))
+    });
+    let input = strings(LONG, MANY, false);
+    group.bench_function("all_end_with", |b| {
+        b.iter(|| black_box(&input).all_end_with(LONG))
+    });
+    group.bench_function("std_ends", |b| {
+        b.iter(|| black_box(&input).par_iter().all(|s| s.ends_with(LONG)))
+    });
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    str_prefix,
+    str_suffix,
+    raw,
+    worst_case,
+    duplicates,
+    identical,
+    pair,
+    hierarchical,
+    dedup,
+    strip,
+    chunked,
+    starts_with
+);
+criterion_main!(benches);
@@ -0,0 +1,45 @@
+use fastxfix::CommonStr;
+use std::hint::black_box;
+use ya_rand::*;
+
+const COMMON: &str = "id_";
+const SIZE: usize = 10_000_000;
+const CHUNKS: [usize; 6] = [1 << 6, 1 << 8, 1 << 10, 1 << 12, 1 << 14, 1 << 16];
+
+fn main() {
+    // Initialize rayon's global threadpool.
+    rayon::join(|| black_box(()), || black_box(()));
+    let strings = gen_strings(SIZE);
+
+    let (prefix, secs) = time(|| strings.common_prefix_ref());
+    assert_eq!(prefix, Some(COMMON));
+    println!("Default reduction of {SIZE} short Strings in {secs:.4} seconds");
+
+    for chunk in CHUNKS {
+        let (prefix, secs) = time(|| strings.common_prefix_chunked(chunk));
+        assert_eq!(prefix, Some(COMMON));
+        println!("Chunked ({chunk}) reduction of {SIZE} short Strings in {secs:.4} seconds");
+    }
+}
+
+fn gen_strings(size: usize) -> Vec<String> {
+    let mut rng = new_rng();
+    let mut vec = vec![String::with_capacity(16); size];
+    vec.iter_mut().for_each(|v| {
+        v.push_str(COMMON);
+        v.push_str(&rng.bits(16).to_string());
+    });
+    vec
+}
+
+fn time<F, T>(func: F) -> (T, f64)
+where
+    F: FnOnce() -> T,
+{
+    let start = std::time::Instant::now();
+    let result = func();
+    let time_delta = std::time::Instant::now()
+        .duration_since(start)
+        .as_secs_f64();
+    (result, time_delta)
+}
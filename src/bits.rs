@@ -0,0 +1,110 @@
+/*!
+Contains the [`CommonBits`] trait, which finds common prefixes of packed bitsets.
+
+Collections of `bool` slices already work with [`CommonRaw`](crate::CommonRaw), but spend
+a whole byte on every bit. Packing the bits into `u64` words instead allows comparing
+64 of them at a time, which is what this trait does.
+*/
+
+use crate::reduce_by;
+use rayon::prelude::*;
+
+const WORD_BITS: usize = u64::BITS as usize;
+
+/// Trait for finding the length of the longest common prefix of bitsets packed
+/// into `u64` words, for any 2D collection of them.
+///
+/// Bit `i` of a bitset is bit `i % 64` of word `i / 64`, counting from the least
+/// significant bit. This is how bits get packed when building each word by
+/// shifting bit `i` left by `i % 64`.
+pub trait CommonBits {
+    /// Returns the amount of leading bits all bitsets have in common, only
+    /// considering the first `len` bits of each.
+    ///
+    /// Bitsets with fewer than `len` bits (less than `len / 64` words, rounded up)
+    /// only take part with the bits they have. Bits past `len` in the final word
+    /// are ignored, so they don't need to be cleared beforehand.
+    ///
+    /// Returns 0 when the collection is empty or nothing is in common.
+    fn common_prefix_bits(&self, len: usize) -> usize;
+}
+
+impl<C: ?Sized, T> CommonBits for C
+where
+    for<'a> &'a C: IntoParallelIterator<Item = &'a T>,
+    T: AsRef<[u64]> + Sync,
+{
+    fn common_prefix_bits(&self, len: usize) -> usize {
+        let iter = self.into_par_iter().map(|words| {
+            let words = words.as_ref();
+            (words, len.min(words.len().saturating_mul(WORD_BITS)))
+        });
+        reduce_by(iter, |(a, a_len), (b, b_len)| {
+            let common = common_bits(a, b).min(a_len).min(b_len);
+            (common > 0).then_some((a, common))
+        })
+        .map_or(0, |(_, common)| common)
+    }
+}
+
+/// Returns the amount of leading bits `a` and `b` have in common,
+/// where every word of the shorter one matches the longer one
+/// counting as all of its bits being common.
+fn common_bits(a: &[u64], b: &[u64]) -> usize {
+    let words = a.iter().zip(b).take_while(|(a, b)| a == b).count();
+    let partial = match a.get(words).zip(b.get(words)) {
+        Some((a, b)) => (a ^ b).trailing_zeros() as usize,
+        None => 0,
+    };
+    words * WORD_BITS + partial
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CommonBits;
+
+    /// Packs `bits` the way [`CommonBits`] expects.
+    fn pack(bits: &[bool]) -> Vec<u64> {
+        let mut words = vec![0; bits.len().div_ceil(64)];
+        for (i, &bit) in bits.iter().enumerate() {
+            words[i / 64] |= (bit as u64) << (i % 64);
+        }
+        words
+    }
+
+    #[test]
+    fn matches_bools() {
+        let len = 200;
+        let base: Vec<bool> = (0..len).map(|i| i % 3 == 0 || i % 7 == 0).collect();
+        for diverge in [0, 1, 63, 64, 65, 127, 128, 199] {
+            let mut other = base.clone();
+            other[diverge] = !other[diverge];
+            let bools = [base.clone(), other.clone()];
+            let packed = [pack(&base), pack(&other)];
+            let expected = crate::CommonRaw::common_prefix_raw_len_or_zero(&bools);
+            assert_eq!(expected, diverge);
+            assert_eq!(packed.common_prefix_bits(len), diverge);
+        }
+        let packed = vec![pack(&base); 1 << 12];
+        assert_eq!(packed.common_prefix_bits(len), len);
+    }
+
+    #[test]
+    fn partial_words() {
+        // Only the low 3 bits of the final word match, so anything past them
+        // is ignored as long as `len` ends before the mismatch.
+        let input = [vec![u64::MAX, 0b0101], vec![u64::MAX, !0b0010]];
+        assert_eq!(input.common_prefix_bits(67), 67);
+        assert_eq!(input.common_prefix_bits(66), 66);
+        assert_eq!(input.common_prefix_bits(1000), 67);
+        // The shorter bitset ends after 64 bits.
+        let input = [vec![7], vec![7, 1]];
+        assert_eq!(input.common_prefix_bits(128), 64);
+        assert_eq!(input.common_prefix_bits(10), 10);
+        let input = [[0b10_u64], [0b11]];
+        assert_eq!(input.common_prefix_bits(64), 0);
+        assert_eq!(input.common_prefix_bits(0), 0);
+        let input: [Vec<u64>; 0] = [];
+        assert_eq!(input.common_prefix_bits(64), 0);
+    }
+}
@@ -0,0 +1,141 @@
+/*!
+Contains the [`CommonRawBorrowed`] and [`CommonStrBorrowed`] traits, for collections of
+slices/strings borrowed from some external buffer (e.g. a memory-mapped file).
+
+[`CommonRaw`](crate::CommonRaw) is implemented for any collection `C` where
+`for<'a> &'a C: IntoParallelIterator<Item = &'a T>`. When `T` is itself a reference
+`&'b [U]`, that bound has to hold for *every* `'a`, which the compiler can only prove
+when `'b` is `'static`. So a `Vec<&[u8]>` pointing into a local buffer doesn't get
+[`CommonRaw`](crate::CommonRaw) at all.
+
+This trait is implemented directly on `[&'b [T]]` instead, which `Vec<&'b [T]>` and
+`[&'b [T]; N]` both dereference to, and its results borrow from the underlying buffer
+(lifetime `'b`) rather than from the collection holding the slices.
+[`CommonStrBorrowed`] does the same for `[&'b str]`.
+
+Elements borrowing from different places (say, a `&'static str` next to a borrow of a
+local `String`) all get shortened to the same lifetime `'b` when they're put into one
+collection, which the compiler picks to be no longer than the shortest of them. A
+result borrowed from `'b` therefore can't outlive any of the elements it could have
+come from, even though the finders always return part of one specific element.
+*/
+
+use crate::finder::*;
+use crate::reduce_common;
+use rayon::prelude::*;
+
+/// Trait for finding the longest common prefix/suffix of slices which
+/// borrow from some external buffer, without tying the result to the
+/// lifetime of the collection.
+pub trait CommonRawBorrowed<'b, T> {
+    /// Returns the longest common prefix of all slices, borrowed from
+    /// whichever buffer the slice it came from points into.
+    ///
+    /// Returns `None` when there is no common prefix.
+    fn common_prefix_raw_borrowed(&self) -> Option<&'b [T]>;
+
+    /// Returns the longest common suffix of all slices, borrowed from
+    /// whichever buffer the slice it came from points into.
+    ///
+    /// Returns `None` when there is no common suffix.
+    fn common_suffix_raw_borrowed(&self) -> Option<&'b [T]>;
+}
+
+impl<'b, T> CommonRawBorrowed<'b, T> for [&'b [T]]
+where
+    T: Eq + Sync,
+{
+    fn common_prefix_raw_borrowed(&self) -> Option<&'b [T]> {
+        reduce_common::<GenericPrefix, _, _>(self.par_iter().copied())
+    }
+
+    fn common_suffix_raw_borrowed(&self) -> Option<&'b [T]> {
+        reduce_common::<GenericSuffix, _, _>(self.par_iter().copied())
+    }
+}
+
+/// Trait for finding the longest common prefix/suffix of strings which
+/// borrow from some external buffer, without tying the result to the
+/// lifetime of the collection.
+///
+/// The result can't outlive the shortest-lived of the strings, so this doesn't compile:
+///
+/// ```compile_fail
+/// use fastxfix::CommonStrBorrowed;
+///
+/// let prefix;
+/// {
+///     let local = String::from("prefix_local");
+///     let strings = vec!["prefix_static", local.as_str()];
+///     prefix = strings.common_prefix_borrowed();
+/// }
+/// assert_eq!(prefix, Some("prefix_"));
+/// ```
+pub trait CommonStrBorrowed<'b> {
+    /// Returns the longest common prefix of all strings, borrowed from
+    /// whichever buffer the string it came from points into.
+    ///
+    /// Returns `None` when there is no common prefix.
+    fn common_prefix_borrowed(&self) -> Option<&'b str>;
+
+    /// Returns the longest common suffix of all strings, borrowed from
+    /// whichever buffer the string it came from points into.
+    ///
+    /// Returns `None` when there is no common suffix.
+    fn common_suffix_borrowed(&self) -> Option<&'b str>;
+}
+
+impl<'b> CommonStrBorrowed<'b> for [&'b str] {
+    fn common_prefix_borrowed(&self) -> Option<&'b str> {
+        reduce_common::<StringPrefix, _, _>(self.par_iter().copied())
+    }
+
+    fn common_suffix_borrowed(&self) -> Option<&'b str> {
+        reduce_common::<StringSuffix, _, _>(self.par_iter().copied())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CommonRawBorrowed, CommonStrBorrowed};
+
+    #[test]
+    fn outlives_collection() {
+        let buffer = b"header:one;header:two;header:three".to_vec();
+        let prefix = {
+            let records: Vec<&[u8]> = buffer.split(|&b| b == b';').collect();
+            records.common_prefix_raw_borrowed().unwrap()
+        };
+        // The collection is gone, but the result still points into the buffer.
+        assert_eq!(prefix, b"header:");
+        assert!(buffer.as_ptr_range().contains(&prefix.as_ptr()));
+
+        let records = [&buffer[..10], &buffer[22..]];
+        let suffix = records.common_suffix_raw_borrowed().unwrap();
+        assert_eq!(suffix, b"e");
+        assert!(buffer.as_ptr_range().contains(&suffix.as_ptr()));
+
+        let records: Vec<&[u8]> = vec![b"abc", b"xyz"];
+        assert!(records.common_prefix_raw_borrowed().is_none());
+        assert!(records[..0].common_suffix_raw_borrowed().is_none());
+    }
+
+    #[test]
+    fn mixed_lifetimes() {
+        let text = String::from("header:one\nheader:two\nheader:three");
+        let prefix = {
+            let lines: Vec<&str> = text.lines().collect();
+            lines.common_prefix_borrowed().unwrap()
+        };
+        assert_eq!(prefix, "header:");
+        assert!(text.as_bytes().as_ptr_range().contains(&prefix.as_ptr()));
+
+        // A `'static` string mixed in just gets shortened to the lifetime of `text`.
+        let strings = ["header:static", &text[..10]];
+        assert_eq!(strings.common_prefix_borrowed().unwrap(), "header:");
+        assert_eq!(strings.common_suffix_borrowed(), None);
+        let strings = ["x_one", &text[3..10]];
+        assert_eq!(strings.common_suffix_borrowed().unwrap(), "one");
+        assert!(strings[..0].common_prefix_borrowed().is_none());
+    }
+}
@@ -0,0 +1,19 @@
+/*!
+Contains the [`Cancelled`] error, returned by queries which were stopped early
+through a cancellation flag.
+*/
+
+use std::error::Error;
+use std::fmt;
+
+/// Error returned when a cancellable query was stopped before it finished.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Cancelled;
+
+impl fmt::Display for Cancelled {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("query was cancelled before it finished")
+    }
+}
+
+impl Error for Cancelled {}
@@ -0,0 +1,102 @@
+/*!
+Contains `const fn` finders, for computing common prefixes/suffixes of string literals
+at compile time.
+
+These compare one byte at a time, since nothing the runtime finders use to go faster
+is available in `const` contexts. Prefer the regular methods for anything at runtime.
+*/
+
+/// Returns the longest common prefix of `a` and `b`, borrowed from `a`.
+/// Usable in `const` contexts.
+///
+/// Returns an empty string when there is no common prefix.
+///
+/// ```
+/// use fastxfix::const_common_prefix;
+///
+/// const ROUTE: &str = const_common_prefix("/api/v1/users", "/api/v1/posts");
+/// assert_eq!(ROUTE, "/api/v1/");
+/// ```
+pub const fn const_common_prefix<'a>(a: &'a str, b: &str) -> &'a str {
+    let (a_bytes, b_bytes) = (a.as_bytes(), b.as_bytes());
+    let mut end = 0;
+    while end < a_bytes.len() && end < b_bytes.len() && a_bytes[end] == b_bytes[end] {
+        end += 1;
+    }
+    while !a.is_char_boundary(end) {
+        end -= 1;
+    }
+    a.split_at(end).0
+}
+
+/// Returns the longest common suffix of `a` and `b`, borrowed from `a`.
+/// Usable in `const` contexts.
+///
+/// Returns an empty string when there is no common suffix.
+///
+/// ```
+/// use fastxfix::const_common_suffix;
+///
+/// const EXT: &str = const_common_suffix("config.toml", "Cargo.toml");
+/// assert_eq!(EXT, ".toml");
+/// ```
+pub const fn const_common_suffix<'a>(a: &'a str, b: &str) -> &'a str {
+    let (a_bytes, b_bytes) = (a.as_bytes(), b.as_bytes());
+    let mut end = 0;
+    while end < a_bytes.len()
+        && end < b_bytes.len()
+        && a_bytes[a_bytes.len() - 1 - end] == b_bytes[b_bytes.len() - 1 - end]
+    {
+        end += 1;
+    }
+    let mut begin = a_bytes.len() - end;
+    while !a.is_char_boundary(begin) {
+        begin += 1;
+    }
+    a.split_at(begin).1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{const_common_prefix, const_common_suffix};
+    use crate::CommonStr;
+
+    #[test]
+    fn const_eval() {
+        let prefix = const { const_common_prefix("réservé", "résumé") };
+        assert_eq!(prefix, "rés");
+        let suffix = const { const_common_suffix("x_ésumé", "résumé") };
+        assert_eq!(suffix, "ésumé");
+        // Both strings share the first byte of 'é' and 'è', but not the whole char.
+        let prefix = const { const_common_prefix("é", "è") };
+        assert_eq!(prefix, "");
+        let suffix = const { const_common_suffix("aé", "bè") };
+        assert_eq!(suffix, "");
+        let prefix = const { const_common_prefix("same", "same") };
+        assert_eq!(prefix, "same");
+        let prefix = const { const_common_prefix("", "abc") };
+        assert_eq!(prefix, "");
+    }
+
+    #[test]
+    fn matches_runtime() {
+        let pairs = [
+            ["this is 😀 text", "this is 😃 text"],
+            ["abc", "abd"],
+            ["prefix_only", "prefix"],
+            ["", ""],
+            ["ü", "u"],
+        ];
+        for pair in pairs {
+            let [a, b] = pair;
+            assert_eq!(
+                const_common_prefix(a, b),
+                pair.common_prefix_ref().unwrap_or("")
+            );
+            assert_eq!(
+                const_common_suffix(a, b),
+                pair.common_suffix_ref().unwrap_or("")
+            );
+        }
+    }
+}
@@ -0,0 +1,97 @@
+/*!
+Contains the [`CommonDeque`] trait, which allows finding the longest common prefix/suffix
+of collections of [`VecDeque`]s.
+
+A [`VecDeque`] is a ring buffer, so its elements generally aren't stored contiguously and it
+can't be referenced as a slice, which [`CommonRaw`](crate::CommonRaw) requires. Instead, the
+deques are compared element by element through their iterators. This is slower than comparing
+slices, since nothing can be compared in chunks, but avoids having to make every deque contiguous
+first. The outer collection still needs to be iterable in parallel.
+*/
+
+use crate::finder::*;
+use crate::reduce_by;
+use rayon::prelude::*;
+use std::collections::VecDeque;
+
+/// Trait for finding the longest common prefix/suffix of any
+/// 2D collection whose elements are [`VecDeque`]s.
+pub trait CommonDeque<T: Clone> {
+    /// Returns the longest common prefix of all deques.
+    ///
+    /// Returns `None` when there is no common prefix.
+    fn common_prefix_deque(&self) -> Option<Vec<T>>;
+
+    /// Returns the longest common suffix of all deques.
+    ///
+    /// Returns `None` when there is no common suffix.
+    fn common_suffix_deque(&self) -> Option<Vec<T>>;
+}
+
+impl<C: ?Sized, T> CommonDeque<T> for C
+where
+    for<'a> &'a C: IntoParallelIterator<Item = &'a VecDeque<T>>,
+    T: Clone + Eq + Sync,
+{
+    fn common_prefix_deque(&self) -> Option<Vec<T>> {
+        // Partial results are a deque along with the length of its prefix
+        // which is still in common.
+        let iter = self.into_par_iter().map(|d| (d, d.len()));
+        let (deque, len) = reduce_by(iter, |(a, a_len), (b, b_len)| {
+            let a_iter = a.iter().take(a_len);
+            let b_iter = b.iter().take(b_len);
+            let len = a_iter.zip(b_iter).count_eq();
+            (len > 0).then_some((a, len))
+        })?;
+        (len > 0).then(|| deque.iter().take(len).cloned().collect())
+    }
+
+    fn common_suffix_deque(&self) -> Option<Vec<T>> {
+        let iter = self.into_par_iter().map(|d| (d, d.len()));
+        let (deque, len) = reduce_by(iter, |(a, a_len), (b, b_len)| {
+            let a_iter = a.iter().rev().take(a_len);
+            let b_iter = b.iter().rev().take(b_len);
+            let len = a_iter.zip(b_iter).count_eq();
+            (len > 0).then_some((a, len))
+        })?;
+        (len > 0).then(|| deque.range(deque.len() - len..).cloned().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CommonDeque;
+    use std::collections::VecDeque;
+
+    /// Builds a deque holding `items` which wraps around its buffer.
+    fn wrapped(items: &[u8]) -> VecDeque<u8> {
+        let (front, back) = items.split_at(items.len() / 2);
+        let mut deque = VecDeque::with_capacity(items.len());
+        deque.extend(back);
+        for &item in front.iter().rev() {
+            deque.push_front(item);
+        }
+        assert!(!deque.as_slices().1.is_empty() || front.is_empty());
+        deque
+    }
+
+    #[test]
+    fn deques() {
+        let input = vec![
+            wrapped(b"ring_buffer_one"),
+            wrapped(b"ring_buffer_two"),
+            VecDeque::from(b"ring_bell_two".to_vec()),
+        ];
+        assert_eq!(input.common_prefix_deque().unwrap(), b"ring_b");
+        assert!(input.common_suffix_deque().is_none());
+        let input = &input[1..];
+        assert_eq!(input.common_suffix_deque().unwrap(), b"_two");
+
+        let input = vec![wrapped(b"abc"), VecDeque::new()];
+        assert!(input.common_prefix_deque().is_none());
+        let input = vec![wrapped(b"only")];
+        assert_eq!(input.common_suffix_deque().unwrap(), b"only");
+        let input: Vec<VecDeque<u8>> = Vec::new();
+        assert!(input.common_prefix_deque().is_none());
+    }
+}
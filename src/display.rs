@@ -0,0 +1,103 @@
+/*!
+Contains the [`CommonDisplay`] trait, which finds common prefixes/suffixes of the
+textual representations of values, without having to collect them as strings first.
+*/
+
+use crate::finder::*;
+use crate::keep_common;
+use rayon::prelude::*;
+use std::fmt::{Display, Write};
+
+/// Trait for finding the longest common prefix/suffix of the [`Display`] output of
+/// every value in any 2D collection.
+///
+/// Every value is rendered into a scratch buffer which is reused by each rayon job,
+/// so only the running result of a job gets its own allocation.
+pub trait CommonDisplay {
+    /// Returns the longest common prefix of the rendered values.
+    ///
+    /// Returns `None` when there is no common prefix.
+    fn common_prefix_display(&self) -> Option<String>;
+
+    /// Returns the longest common suffix of the rendered values.
+    ///
+    /// Returns `None` when there is no common suffix.
+    fn common_suffix_display(&self) -> Option<String>;
+}
+
+impl<C: ?Sized, T> CommonDisplay for C
+where
+    for<'a> &'a C: IntoParallelIterator<Item = &'a T>,
+    T: Display + Sync,
+{
+    fn common_prefix_display(&self) -> Option<String> {
+        display_common::<StringPrefix, _>(self.into_par_iter())
+    }
+
+    fn common_suffix_display(&self) -> Option<String> {
+        display_common::<StringSuffix, _>(self.into_par_iter())
+    }
+}
+
+/// Folds the rendered items of `iter` the same way `bridge_common` folds owned
+/// strings, except each job renders into a single scratch buffer.
+fn display_common<'a, F, T>(iter: impl ParallelIterator<Item = &'a T>) -> Option<String>
+where
+    F: Finder<str>,
+    T: Display + Sync + 'a,
+{
+    iter.try_fold(
+        || (None, String::new()),
+        |(previous, mut scratch): (Option<String>, String), current| {
+            scratch.clear();
+            write!(scratch, "{current}").expect("a Display implementation returned an error");
+            let common = match previous {
+                Some(prev) => keep_common::<F>(prev, &scratch)?,
+                None => scratch.clone(),
+            };
+            Some((Some(common), scratch))
+        },
+    )
+    .map(|partial| partial.map(|(common, _)| common))
+    .try_reduce(
+        || None,
+        |a, b| match (a, b) {
+            (Some(a), Some(b)) => keep_common::<F>(a, &b).map(Some),
+            (Some(common), None) | (None, Some(common)) => Some(Some(common)),
+            (None, None) => Some(None),
+        },
+    )
+    .flatten()
+    .filter(|common| !common.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CommonDisplay;
+    use std::net::Ipv4Addr;
+
+    #[test]
+    fn numbers() {
+        let input = [1234_u64, 1238, 123];
+        assert_eq!(input.common_prefix_display().unwrap(), "123");
+        assert_eq!(input.common_suffix_display(), None);
+        let input = [-1.5_f64, -2.5, -10.5];
+        assert_eq!(input.common_prefix_display().unwrap(), "-");
+        assert_eq!(input.common_suffix_display().unwrap(), ".5");
+        let input = [7_u8, 8];
+        assert_eq!(input.common_prefix_display(), None);
+        let input: Vec<u32> = Vec::new();
+        assert_eq!(input.common_prefix_display(), None);
+        // Renders as a single empty string.
+        assert_eq!([""].common_suffix_display(), None);
+    }
+
+    #[test]
+    fn many() {
+        let input: Vec<u64> = (0..1 << 15).map(|i| 900_000_000 + i * 1000).collect();
+        assert_eq!(input.common_prefix_display().unwrap(), "9");
+        assert_eq!(input.common_suffix_display().unwrap(), "000");
+        let input: Vec<Ipv4Addr> = (0..=255).map(|i| Ipv4Addr::new(10, 0, 3, i)).collect();
+        assert_eq!(input.common_prefix_display().unwrap(), "10.0.3.");
+    }
+}
@@ -0,0 +1,94 @@
+/*!
+Contains [`DynStr`], which makes collections of boxed trait objects usable with
+[`CommonStr`](crate::CommonStr).
+
+`Box<dyn AsRef<str> + Sync>` can't be referenced as `str` directly: the standard library
+only implements `AsRef<T>` for `Box<T>` itself, not `AsRef<U>` for every `U` the boxed value
+can be referenced as (unlike for `&T`). So calling `as_ref` on the box gives the trait object
+rather than a `str`, and collections of such boxes don't satisfy the `T: AsRef<str>` bound.
+[`DynStr`] wraps the box to forward `as_ref` to the boxed value instead.
+*/
+
+use std::fmt;
+
+/// A boxed `dyn AsRef<str> + Sync`, which can be referenced as `str`.
+///
+/// Collections of these get every method of [`CommonStr`](crate::CommonStr). Existing
+/// slices of boxes can be viewed as slices of [`DynStr`] through [`DynStr::from_boxes`],
+/// without moving or copying anything.
+///
+/// ```
+/// use fastxfix::{CommonStr, DynStr};
+///
+/// let boxes: Vec<Box<dyn AsRef<str> + Sync>> =
+///     vec![Box::new(String::from("dyn_one")), Box::new("dyn_two")];
+/// assert_eq!(DynStr::from_boxes(&boxes).common_prefix().unwrap(), "dyn_");
+/// ```
+#[repr(transparent)]
+pub struct DynStr(pub Box<dyn AsRef<str> + Sync>);
+
+impl DynStr {
+    /// Boxes `value` into a [`DynStr`].
+    pub fn new<T: AsRef<str> + Sync + 'static>(value: T) -> Self {
+        Self(Box::new(value))
+    }
+
+    /// Views a slice of boxes as a slice of [`DynStr`].
+    pub fn from_boxes(boxes: &[Box<dyn AsRef<str> + Sync>]) -> &[Self] {
+        // SAFETY: `DynStr` is a `repr(transparent)` wrapper around the
+        // exact type of the slice's elements, so both have the same layout.
+        unsafe { &*(boxes as *const [Box<dyn AsRef<str> + Sync>] as *const [Self]) }
+    }
+}
+
+impl AsRef<str> for DynStr {
+    fn as_ref(&self) -> &str {
+        (*self.0).as_ref()
+    }
+}
+
+impl From<Box<dyn AsRef<str> + Sync>> for DynStr {
+    fn from(boxed: Box<dyn AsRef<str> + Sync>) -> Self {
+        Self(boxed)
+    }
+}
+
+impl fmt::Debug for DynStr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_ref(), f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DynStr;
+    use crate::CommonStr;
+    use std::borrow::Cow;
+    use std::sync::Arc;
+
+    #[test]
+    fn mixed_sources() {
+        let boxes: Vec<Box<dyn AsRef<str> + Sync>> = vec![
+            Box::new(String::from("source: string.")),
+            Box::new("source: literal."),
+            Box::new(Arc::<str>::from("source: arc.")),
+            Box::new(Cow::Borrowed("source: cow.")),
+        ];
+        let view = DynStr::from_boxes(&boxes);
+        assert_eq!(view.common_prefix().unwrap(), "source: ");
+        assert_eq!(view.common_suffix().unwrap(), ".");
+        // The view borrows straight from the boxed values.
+        let prefix = view.common_prefix_ref().unwrap();
+        assert!(
+            boxes
+                .iter()
+                .any(|b| (**b).as_ref().as_ptr() == prefix.as_ptr())
+        );
+
+        let owned: Vec<DynStr> = boxes.into_iter().map(DynStr::from).collect();
+        assert_eq!(owned.common_prefix().unwrap(), "source: ");
+        let owned = [DynStr::new("abc"), DynStr::new(String::from("xyz"))];
+        assert_eq!(owned.common_prefix(), None);
+        assert_eq!(format!("{:?}", owned[0]), "\"abc\"");
+    }
+}
@@ -0,0 +1,88 @@
+/*!
+Contains [`common_prefix_raw_ptrs`], an entry point for buffers received over FFI
+as pointer/length pairs, which would otherwise have to be turned into a
+collection of slices before any of the traits could be used.
+*/
+
+use crate::finder::*;
+use crate::reduce_common;
+use rayon::prelude::*;
+use std::{ptr, slice};
+
+/// A pointer/length pair, which can be shared between threads
+/// for as long as the buffer it describes is only read.
+#[repr(transparent)]
+struct RawPart((*const u8, usize));
+
+// SAFETY: The caller of `common_prefix_raw_ptrs` guarantees that every
+// buffer stays valid and unmodified for the duration of the call.
+unsafe impl Sync for RawPart {}
+
+/// Returns the length of the longest common prefix of the buffers described by
+/// `ptrs`, along with a pointer to the start of the buffer it was found in.
+///
+/// This runs the same byte-level comparisons as [`CommonRaw`](crate::CommonRaw) does for
+/// collections of `[u8]`. The returned pointer is one of the pointers in `ptrs`, which
+/// one being unspecified. When the collection is empty or there is no common prefix,
+/// `(0, null)` is returned.
+///
+/// # Safety
+///
+/// For every `(ptr, len)` in `ptrs`, `ptr` must be valid for reads of `len` bytes,
+/// and must not be null (even when `len` is 0). The buffers must stay alive and
+/// must not be written to until this returns. They are only ever read, so they may
+/// overlap, or even be the same buffer.
+///
+/// ```
+/// let (a, b) = (b"ffi_prefix_one", b"ffi_prefix_two");
+/// let ptrs = [(a.as_ptr(), a.len()), (b.as_ptr(), b.len())];
+/// let (len, winner) = unsafe { fastxfix::common_prefix_raw_ptrs(&ptrs) };
+/// assert_eq!(len, 11);
+/// assert!(winner == a.as_ptr() || winner == b.as_ptr());
+/// ```
+pub unsafe fn common_prefix_raw_ptrs(ptrs: &[(*const u8, usize)]) -> (usize, *const u8) {
+    // SAFETY: `RawPart` is a `repr(transparent)` wrapper around
+    // the element type of `ptrs`, so both have the same layout.
+    let parts = unsafe { &*(ptrs as *const [(*const u8, usize)] as *const [RawPart]) };
+    let iter = parts.par_iter().map(|RawPart((ptr, len))| {
+        // SAFETY: Upheld by the caller.
+        unsafe { slice::from_raw_parts(*ptr, *len) }
+    });
+    match reduce_common::<BytePrefix, _, _>(iter) {
+        Some(prefix) => (prefix.len(), prefix.as_ptr()),
+        None => (0, ptr::null()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::common_prefix_raw_ptrs;
+    use crate::CommonRaw;
+
+    #[test]
+    fn matches_slices() {
+        let buffers: Vec<Vec<u8>> = (0..1 << 12)
+            .map(|i| format!("raw_ptrs_{i}").into_bytes())
+            .collect();
+        let ptrs: Vec<_> = buffers.iter().map(|b| (b.as_ptr(), b.len())).collect();
+        let (len, winner) = unsafe { common_prefix_raw_ptrs(&ptrs) };
+        assert_eq!(len, buffers.common_prefix_raw_ref().unwrap().len());
+        assert!(ptrs.iter().any(|&(ptr, _)| ptr == winner));
+
+        // The same buffer, and a slice of it, overlap.
+        let whole = b"overlapping";
+        let ptrs = [(whole.as_ptr(), whole.len()), (whole.as_ptr(), 4)];
+        assert_eq!(unsafe { common_prefix_raw_ptrs(&ptrs) }.0, 4);
+        let ptrs = [(whole.as_ptr(), whole.len()), (whole[1..].as_ptr(), 4)];
+        assert_eq!(
+            unsafe { common_prefix_raw_ptrs(&ptrs) },
+            (0, std::ptr::null())
+        );
+        // A single empty buffer has no common prefix either.
+        let ptrs = [(whole.as_ptr(), 0)];
+        let (len, winner) = unsafe { common_prefix_raw_ptrs(&ptrs) };
+        assert_eq!(len, 0);
+        assert!(winner.is_null());
+        assert!(unsafe { common_prefix_raw_ptrs(&[]) }.1.is_null());
+    }
+}
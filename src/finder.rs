@@ -16,23 +16,98 @@ equivalent bytes the two strings have. After this, we just check byte-by-byte fr
 equal chunks ended to determine the total amount of equal consecutive bytes in the prefix/suffix,
 and now we have an index which can be adjusted to the nearest char boundary and used for slicing.
 
+Before any of that, every finder checks whether the two references point at the exact same data.
+Deduplicated inputs (e.g. many `Arc<str>` sharing one allocation) then cost O(1) per pair instead
+of a full comparison.
+
+The string finders additionally check whether two strings of equal length are entirely equal,
+which is a plain `memcmp` that beats the chunked comparison. This targets collections where
+every string is the same, without sharing an allocation. It assumes differing strings mostly
+differ in length, or differ early: whenever the running result happens to be as long as
+the next string, a mismatch near their end gets scanned twice, once by the `memcmp` and
+once more by the comparison that finds where it is.
+
+The chunk size defaults to 16 bytes, but can be set to 16, 32, or 64 by building with
+`--cfg fastxfix_chunk_size="N"` (e.g. through `RUSTFLAGS`), for benchmarking other hardware.
+
+This chunking is controlled by the `simd` feature (enabled by default). Disabling it swaps the
+chunked comparisons for plain byte-by-byte ones, for targets where the vectorized code is
+undesirable. No intrinsics are used either way, so both variants compile on every target.
+
+On nightly, building with `--cfg fastxfix_portable_simd` replaces the chunked loop with
+explicit `std::simd` vectors. Each pair of chunks gets compared into a bitmask of
+mismatching lanes, whose first (or last) set bit is the exact position of the mismatch,
+so the bytes of the mismatching chunk never need to be compared one by one. It's still a single implementation for every
+target, with the backend selection left to LLVM.
+
 # Safety
 
 All implementations of [`Finder`] use `unsafe` when indexing the final slice/str being returned.
 This indexing is safe because the index itself is directly derived from the minimum length of the two
 slices/strs being compared.
+The one exception is [`AsciiPrefix`], which relies on its caller to only pass it ASCII
+strings, as every byte index into those lies on a char boundary.
+
+Enabling the `debug-checks` feature swaps every unchecked operation for its checked counterpart,
+so a mistake in the index math panics with a clear message instead of silently causing UB.
 */
 
+#[cfg(feature = "profiling")]
+use crate::profiling;
+use std::num::NonZeroUsize;
+use std::ptr;
+#[cfg(all(feature = "simd", fastxfix_portable_simd))]
+use std::simd::prelude::*;
+
+/// Indexes `$s` with `$range` without bounds checks, unless
+/// the `debug-checks` feature is enabled.
+macro_rules! get_unchecked {
+    ($s:expr, $range:expr) => {{
+        #[cfg(feature = "debug-checks")]
+        let sliced = $s
+            .get($range)
+            .expect("fastxfix computed an out of bounds or non char boundary index");
+        #[cfg(not(feature = "debug-checks"))]
+        let sliced = unsafe { $s.get_unchecked($range) };
+        sliced
+    }};
+}
+pub(crate) use get_unchecked;
+
+/// Converts the length of a result returned by a [`Finder`] into a [`NonZeroUsize`].
+/// Finders never return empty results, and reducing a collection of a single empty
+/// value gives `None` rather than that value, so this is only checked when the
+/// `debug-checks` feature is enabled.
+#[inline]
+pub fn nonzero_len(len: usize) -> NonZeroUsize {
+    #[cfg(feature = "debug-checks")]
+    return NonZeroUsize::new(len).expect("fastxfix produced an empty result");
+    #[cfg(not(feature = "debug-checks"))]
+    unsafe {
+        NonZeroUsize::new_unchecked(len)
+    }
+}
+
 /// Equivalent to `__m128i::BITS` / `u8::BITS`. This allows the
 /// string prefix/suffix methods to autovectorize their operations,
 /// which provides a >50%+ speed increase on my machine.
 ///
 /// Testing suggests that this doesn't scale all that well to larger
 /// vector registers, even in examples containing relatively long
-/// common prefixes/suffixes.
+/// common prefixes/suffixes. Since that was only tested on my machine,
+/// it can be overridden by building with `--cfg fastxfix_chunk_size="N"`,
+/// where `N` is one of 16, 32, or 64.
+#[cfg(all(
+    feature = "simd",
+    not(any(fastxfix_chunk_size = "32", fastxfix_chunk_size = "64"))
+))]
 const CHUNK_SIZE: usize = 128 / 8;
+#[cfg(all(feature = "simd", fastxfix_chunk_size = "32"))]
+const CHUNK_SIZE: usize = 32;
+#[cfg(all(feature = "simd", fastxfix_chunk_size = "64"))]
+const CHUNK_SIZE: usize = 64;
 
-trait EqCounter {
+pub trait EqCounter {
     fn count_eq(self) -> usize;
 }
 
@@ -52,52 +127,440 @@ pub trait Finder<T: ?Sized> {
     fn common<'a>(a: &'a T, b: &T) -> Option<&'a T>;
 }
 
+/// Counts the amount of consecutive equal bytes at the start of both slices.
+#[cfg(all(feature = "simd", not(fastxfix_portable_simd)))]
+#[inline]
+fn count_eq_prefix(a: &[u8], b: &[u8]) -> usize {
+    count_eq_prefix_chunked::<CHUNK_SIZE, _>(a, b)
+}
+
+/// Counts the amount of consecutive equal bytes at the end of both slices.
+#[cfg(all(feature = "simd", not(fastxfix_portable_simd)))]
+#[inline]
+fn count_eq_suffix(a: &[u8], b: &[u8]) -> usize {
+    count_eq_suffix_chunked::<CHUNK_SIZE, _>(a, b)
+}
+
+/// Counts the amount of consecutive equal bytes at the start of both slices,
+/// comparing [`CHUNK_SIZE`] bytes at a time as vectors for as long as possible.
+#[cfg(all(feature = "simd", fastxfix_portable_simd))]
+#[inline]
+fn count_eq_prefix(a: &[u8], b: &[u8]) -> usize {
+    let mut equal = 0;
+    let mut lanes = None;
+    for (a_chunk, b_chunk) in a.chunks_exact(CHUNK_SIZE).zip(b.chunks_exact(CHUNK_SIZE)) {
+        let mismatches = mismatched_lanes(a_chunk, b_chunk);
+        if mismatches != 0 {
+            lanes = Some(mismatches.trailing_zeros() as usize);
+            break;
+        }
+        equal += 1;
+    }
+    #[cfg(feature = "profiling")]
+    profiling::record_chunks(equal, a.len().min(b.len()) / CHUNK_SIZE);
+    let end = equal * CHUNK_SIZE;
+    if let Some(lanes) = lanes {
+        return end + lanes;
+    }
+
+    let a_rem = a.iter().skip(end);
+    let b_rem = b.iter().skip(end);
+    let tail = a_rem.zip(b_rem).count_eq();
+    #[cfg(feature = "profiling")]
+    profiling::record_bytes(tail, a.len().min(b.len()) - end);
+    end + tail
+}
+
+/// Counts the amount of consecutive equal bytes at the end of both slices,
+/// comparing [`CHUNK_SIZE`] bytes at a time as vectors for as long as possible.
+#[cfg(all(feature = "simd", fastxfix_portable_simd))]
+#[inline]
+fn count_eq_suffix(a: &[u8], b: &[u8]) -> usize {
+    let mut equal = 0;
+    let mut lanes = None;
+    for (a_chunk, b_chunk) in a.rchunks_exact(CHUNK_SIZE).zip(b.rchunks_exact(CHUNK_SIZE)) {
+        let mismatches = mismatched_lanes(a_chunk, b_chunk);
+        if mismatches != 0 {
+            // Only the low `CHUNK_SIZE` bits of the mask are used.
+            lanes = Some(mismatches.leading_zeros() as usize - (64 - CHUNK_SIZE));
+            break;
+        }
+        equal += 1;
+    }
+    #[cfg(feature = "profiling")]
+    profiling::record_chunks(equal, a.len().min(b.len()) / CHUNK_SIZE);
+    let end = equal * CHUNK_SIZE;
+    if let Some(lanes) = lanes {
+        return end + lanes;
+    }
+
+    let a_rem = a.iter().rev().skip(end);
+    let b_rem = b.iter().rev().skip(end);
+    let tail = a_rem.zip(b_rem).count_eq();
+    #[cfg(feature = "profiling")]
+    profiling::record_bytes(tail, a.len().min(b.len()) - end);
+    end + tail
+}
+
+/// Returns a bitmask with bit `i` set when byte `i` of the two
+/// [`CHUNK_SIZE`] byte chunks differs.
+#[cfg(all(feature = "simd", fastxfix_portable_simd))]
+#[inline]
+fn mismatched_lanes(a: &[u8], b: &[u8]) -> u64 {
+    let a = Simd::<u8, CHUNK_SIZE>::from_slice(a);
+    a.simd_ne(Simd::from_slice(b)).to_bitmask()
+}
+
+/// Counts the amount of consecutive equal elements at the start of both slices,
+/// comparing `N` elements at a time for as long as possible.
+#[cfg(any(feature = "simd", test))]
+#[inline]
+fn count_eq_prefix_chunked<const N: usize, T: Eq>(a: &[T], b: &[T]) -> usize {
+    let a_chunks = a.chunks_exact(N);
+    let b_chunks = b.chunks_exact(N);
+    let mut end = a_chunks.zip(b_chunks).count_eq();
+    #[cfg(feature = "profiling")]
+    profiling::record_chunks(end, a.len().min(b.len()) / N);
+    end *= N;
+
+    let a_rem = a.iter().skip(end);
+    let b_rem = b.iter().skip(end);
+    let tail = a_rem.zip(b_rem).count_eq();
+    #[cfg(feature = "profiling")]
+    profiling::record_bytes(tail, a.len().min(b.len()) - end);
+    end + tail
+}
+
+/// Counts the amount of consecutive equal elements at the end of both slices,
+/// comparing `N` elements at a time for as long as possible.
+#[cfg(any(feature = "simd", test))]
+#[inline]
+fn count_eq_suffix_chunked<const N: usize, T: Eq>(a: &[T], b: &[T]) -> usize {
+    let a_chunks = a.rchunks_exact(N);
+    let b_chunks = b.rchunks_exact(N);
+    let mut end = a_chunks.zip(b_chunks).count_eq();
+    #[cfg(feature = "profiling")]
+    profiling::record_chunks(end, a.len().min(b.len()) / N);
+    end *= N;
+
+    let a_rem = a.iter().rev().skip(end);
+    let b_rem = b.iter().rev().skip(end);
+    let tail = a_rem.zip(b_rem).count_eq();
+    #[cfg(feature = "profiling")]
+    profiling::record_bytes(tail, a.len().min(b.len()) - end);
+    end + tail
+}
+
+/// Counts the amount of consecutive equal bytes at the start of both slices.
+#[cfg(not(feature = "simd"))]
+#[inline]
+fn count_eq_prefix(a: &[u8], b: &[u8]) -> usize {
+    count_eq_prefix_bytes(a, b)
+}
+
+/// Counts the amount of consecutive equal bytes at the end of both slices.
+#[cfg(not(feature = "simd"))]
+#[inline]
+fn count_eq_suffix(a: &[u8], b: &[u8]) -> usize {
+    count_eq_suffix_bytes(a, b)
+}
+
+/// Counts the amount of consecutive equal bytes at the start of both slices,
+/// one byte at a time. Always compiled for tests, where it's the reference
+/// the chunked comparisons are checked against.
+#[cfg(any(not(feature = "simd"), test))]
+#[inline]
+fn count_eq_prefix_bytes(a: &[u8], b: &[u8]) -> usize {
+    let end = a.iter().zip(b.iter()).count_eq();
+    #[cfg(feature = "profiling")]
+    profiling::record_bytes(end, a.len().min(b.len()));
+    end
+}
+
+/// Counts the amount of consecutive equal bytes at the end of both slices,
+/// one byte at a time. Always compiled for tests, where it's the reference
+/// the chunked comparisons are checked against.
+#[cfg(any(not(feature = "simd"), test))]
+#[inline]
+fn count_eq_suffix_bytes(a: &[u8], b: &[u8]) -> usize {
+    let end = a.iter().rev().zip(b.iter().rev()).count_eq();
+    #[cfg(feature = "profiling")]
+    profiling::record_bytes(end, a.len().min(b.len()));
+    end
+}
+
 pub struct StringPrefix;
 impl Finder<str> for StringPrefix {
     fn common<'a>(a: &'a str, b: &str) -> Option<&'a str> {
-        let a_bytes = a.as_bytes();
-        let b_bytes = b.as_bytes();
+        if !a.is_empty() && (ptr::eq(a, b) || (a.len() == b.len() && a == b)) {
+            return Some(a);
+        }
+        str_prefix(a, count_eq_prefix(a.as_bytes(), b.as_bytes()))
+    }
+}
 
-        let a_chunks = a_bytes.chunks_exact(CHUNK_SIZE);
-        let b_chunks = b_bytes.chunks_exact(CHUNK_SIZE);
-        let mut end = a_chunks.zip(b_chunks).count_eq();
-        end *= CHUNK_SIZE;
+/// Returns the first `end` bytes of `a`, after moving `end`
+/// back onto a char boundary.
+#[inline]
+fn str_prefix(a: &str, mut end: usize) -> Option<&str> {
+    while !a.is_char_boundary(end) {
+        end -= 1;
+    }
+    match end > 0 {
+        true => Some(get_unchecked!(a, ..end)),
+        false => None,
+    }
+}
 
-        let a_rem = a_bytes.iter().skip(end);
-        let b_rem = b_bytes.iter().skip(end);
-        end += a_rem.zip(b_rem).count_eq();
+pub struct StringSuffix;
+impl Finder<str> for StringSuffix {
+    fn common<'a>(a: &'a str, b: &str) -> Option<&'a str> {
+        if !a.is_empty() && (ptr::eq(a, b) || (a.len() == b.len() && a == b)) {
+            return Some(a);
+        }
+        str_suffix(a, count_eq_suffix(a.as_bytes(), b.as_bytes()))
+    }
+}
 
-        while !a.is_char_boundary(end) {
-            end -= 1;
+/// Returns the last `end` bytes of `a`, after moving the start
+/// of that range forward onto a char boundary.
+#[inline]
+fn str_suffix(a: &str, end: usize) -> Option<&str> {
+    let mut begin = a.len() - end;
+    while !a.is_char_boundary(begin) {
+        begin += 1;
+    }
+    match begin < a.len() {
+        true => Some(get_unchecked!(a, begin..)),
+        false => None,
+    }
+}
+
+/// Counterpart to [`StringPrefix`] for strings which are known to be ASCII.
+/// Every byte of an ASCII string is a char boundary, so the amount of equal
+/// bytes is used as-is, without moving it onto one.
+///
+/// Both strings **must** be ASCII, otherwise the result might end in the
+/// middle of a char. This is only checked in debug builds.
+pub struct AsciiPrefix;
+impl Finder<str> for AsciiPrefix {
+    fn common<'a>(a: &'a str, b: &str) -> Option<&'a str> {
+        debug_assert!(a.is_ascii() && b.is_ascii(), "non-ASCII string");
+        if !a.is_empty() && (ptr::eq(a, b) || (a.len() == b.len() && a == b)) {
+            return Some(a);
         }
+        let end = count_eq_prefix(a.as_bytes(), b.as_bytes());
         match end > 0 {
-            true => Some(unsafe { a.get_unchecked(..end) }),
+            true => Some(get_unchecked!(a, ..end)),
             false => None,
         }
     }
 }
 
-pub struct StringSuffix;
-impl Finder<str> for StringSuffix {
+/// Finds the common prefix of `a` and `b`, treating any two runs of ASCII digits
+/// as equal no matter their contents or lengths. The result is borrowed from `a`,
+/// so it contains `a`'s digits, and always ends on a whole digit run.
+pub struct DigitRunPrefix;
+impl Finder<str> for DigitRunPrefix {
+    fn common<'a>(a: &'a str, b: &str) -> Option<&'a str> {
+        let (a_bytes, b_bytes) = (a.as_bytes(), b.as_bytes());
+        let (mut i, mut j) = (0, 0);
+        while i < a_bytes.len() && j < b_bytes.len() {
+            let (x, y) = (a_bytes[i], b_bytes[j]);
+            if x.is_ascii_digit() && y.is_ascii_digit() {
+                i += digit_run(&a_bytes[i..]);
+                j += digit_run(&b_bytes[j..]);
+            } else if x == y {
+                i += 1;
+                j += 1;
+            } else {
+                break;
+            }
+        }
+        str_prefix(a, i)
+    }
+}
+
+/// Returns the length of the run of ASCII digits at the start of `s`.
+#[inline]
+fn digit_run(s: &[u8]) -> usize {
+    s.iter().take_while(|b| b.is_ascii_digit()).count()
+}
+
+/// Counterpart to [`StringPrefix`] which ignores ASCII case. Non-ASCII bytes
+/// are still compared exactly, so the result is moved back onto a char boundary.
+pub struct AsciiCasePrefix;
+impl Finder<str> for AsciiCasePrefix {
+    fn common<'a>(a: &'a str, b: &str) -> Option<&'a str> {
+        let end = prefix_while(a.as_bytes(), b.as_bytes(), &u8::eq_ignore_ascii_case)
+            .map_or(0, <[u8]>::len);
+        str_prefix(a, end)
+    }
+}
+
+/// Counterpart to [`StringSuffix`] which ignores ASCII case. Non-ASCII bytes
+/// are still compared exactly, so the result is moved forward onto a char boundary.
+pub struct AsciiCaseSuffix;
+impl Finder<str> for AsciiCaseSuffix {
     fn common<'a>(a: &'a str, b: &str) -> Option<&'a str> {
-        let a_bytes = a.as_bytes();
-        let b_bytes = b.as_bytes();
+        let end = suffix_while(a.as_bytes(), b.as_bytes(), &u8::eq_ignore_ascii_case)
+            .map_or(0, <[u8]>::len);
+        str_suffix(a, end)
+    }
+}
+
+/// Strings shorter than this are compared from both ends in
+/// a single loop by [`prefix_and_suffix`].
+const FUSED_LEN: usize = 64;
+
+/// Finds both the common prefix and suffix of `a` and `b`, as
+/// [`StringPrefix`] and [`StringSuffix`] would.
+///
+/// Short strings fit in a couple of cache lines, so a single loop comparing
+/// from both ends at once beats two separate (chunked) scans. Longer strings
+/// just use those scans.
+pub fn prefix_and_suffix<'a>(a: &'a str, b: &str) -> (Option<&'a str>, Option<&'a str>) {
+    if a.len().max(b.len()) >= FUSED_LEN {
+        return (StringPrefix::common(a, b), StringSuffix::common(a, b));
+    }
+    let (a_bytes, b_bytes) = (a.as_bytes(), b.as_bytes());
+    let len = a.len().min(b.len());
+    let (mut prefix, mut suffix) = (len, len);
+    for i in 0..len {
+        if prefix == len && a_bytes[i] != b_bytes[i] {
+            prefix = i;
+        }
+        if suffix == len && a_bytes[a.len() - 1 - i] != b_bytes[b.len() - 1 - i] {
+            suffix = i;
+        }
+        if prefix != len && suffix != len {
+            break;
+        }
+    }
+    (str_prefix(a, prefix), str_suffix(a, suffix))
+}
+
+/// Finds the common prefix of `a` and `b` as [`StringPrefix`] would, along with
+/// the first pair of bytes which differ, if neither string ended before them.
+pub fn prefix_with_diff<'a>(a: &'a str, b: &str) -> (Option<&'a str>, Option<(u8, u8)>) {
+    let end = count_eq_prefix(a.as_bytes(), b.as_bytes());
+    let diff = a.as_bytes().get(end).zip(b.as_bytes().get(end));
+    (str_prefix(a, end), diff.map(|(&x, &y)| (x, y)))
+}
+
+/// Finds the common prefix of `a` and `b` as [`StringPrefix`] would, along with the
+/// amount of bytes compared to find it: every equal byte, plus the one which differed.
+pub fn prefix_with_cost<'a>(a: &'a str, b: &str) -> (Option<&'a str>, u64) {
+    let end = count_eq_prefix(a.as_bytes(), b.as_bytes());
+    let compared = (end + 1).min(a.len().min(b.len()));
+    (str_prefix(a, end), compared as u64)
+}
+
+/// Byte-level counterpart to [`StringPrefix`], which doesn't adjust
+/// its result to lie on a char boundary.
+pub struct BytePrefix;
+impl Finder<[u8]> for BytePrefix {
+    fn common<'a>(a: &'a [u8], b: &[u8]) -> Option<&'a [u8]> {
+        let end = count_eq_prefix(a, b);
+        match end > 0 {
+            true => Some(get_unchecked!(a, ..end)),
+            false => None,
+        }
+    }
+}
+
+/// Byte-level counterpart to [`StringSuffix`], which doesn't adjust
+/// its result to lie on a char boundary.
+pub struct ByteSuffix;
+impl Finder<[u8]> for ByteSuffix {
+    fn common<'a>(a: &'a [u8], b: &[u8]) -> Option<&'a [u8]> {
+        let end = count_eq_suffix(a, b);
+        let begin = a.len() - end;
+        match begin < a.len() {
+            true => Some(get_unchecked!(a, begin..)),
+            false => None,
+        }
+    }
+}
 
-        let a_chunks = a_bytes.rchunks_exact(CHUNK_SIZE);
-        let b_chunks = b_bytes.rchunks_exact(CHUNK_SIZE);
-        let mut end = a_chunks.zip(b_chunks).count_eq();
-        end *= CHUNK_SIZE;
+/// Counts the amount of consecutive equal UTF-16 code units at the start of both
+/// slices, comparing as many bytes at once as [`count_eq_prefix`] does.
+#[inline]
+fn count_eq_prefix_utf16(a: &[u16], b: &[u16]) -> usize {
+    #[cfg(feature = "simd")]
+    return count_eq_prefix_chunked::<{ CHUNK_SIZE / 2 }, _>(a, b);
+    #[cfg(not(feature = "simd"))]
+    a.iter().zip(b).count_eq()
+}
+
+/// Counts the amount of consecutive equal UTF-16 code units at the end of both
+/// slices, comparing as many bytes at once as [`count_eq_suffix`] does.
+#[inline]
+fn count_eq_suffix_utf16(a: &[u16], b: &[u16]) -> usize {
+    #[cfg(feature = "simd")]
+    return count_eq_suffix_chunked::<{ CHUNK_SIZE / 2 }, _>(a, b);
+    #[cfg(not(feature = "simd"))]
+    a.iter().rev().zip(b.iter().rev()).count_eq()
+}
 
-        let a_rem = a_bytes.iter().rev().skip(end);
-        let b_rem = b_bytes.iter().rev().skip(end);
-        end += a_rem.zip(b_rem).count_eq();
+/// Returns the code unit right before the last `end` of `s`, if there is one.
+#[inline]
+fn unit_before(s: &[u16], end: usize) -> Option<&u16> {
+    s.len().checked_sub(end + 1).and_then(|i| s.get(i))
+}
+
+#[inline]
+fn is_high_surrogate(unit: Option<&u16>) -> bool {
+    unit.is_some_and(|unit| (0xD800..0xDC00).contains(unit))
+}
 
+#[inline]
+fn is_low_surrogate(unit: Option<&u16>) -> bool {
+    unit.is_some_and(|unit| (0xDC00..0xE000).contains(unit))
+}
+
+/// UTF-16 counterpart to [`StringPrefix`]. Instead of moving the result onto a char
+/// boundary, it's shortened by one code unit when it would end between the high
+/// and low surrogate of a pair in either slice. Unpaired surrogates are treated
+/// like any other code unit, so this never fails on ill-formed UTF-16.
+pub struct Utf16Prefix;
+impl Finder<[u16]> for Utf16Prefix {
+    fn common<'a>(a: &'a [u16], b: &[u16]) -> Option<&'a [u16]> {
+        if !a.is_empty() && ptr::eq(a, b) {
+            return Some(a);
+        }
+        let mut end = count_eq_prefix_utf16(a, b);
+        if end > 0
+            && is_high_surrogate(a.get(end - 1))
+            && (is_low_surrogate(a.get(end)) || is_low_surrogate(b.get(end)))
+        {
+            end -= 1;
+        }
+        match end > 0 {
+            true => Some(get_unchecked!(a, ..end)),
+            false => None,
+        }
+    }
+}
+
+/// UTF-16 counterpart to [`StringSuffix`], which is shortened by one code unit
+/// when it would start between the high and low surrogate of a pair in either slice.
+pub struct Utf16Suffix;
+impl Finder<[u16]> for Utf16Suffix {
+    fn common<'a>(a: &'a [u16], b: &[u16]) -> Option<&'a [u16]> {
+        if !a.is_empty() && ptr::eq(a, b) {
+            return Some(a);
+        }
+        let end = count_eq_suffix_utf16(a, b);
         let mut begin = a.len() - end;
-        while !a.is_char_boundary(begin) {
+        if begin < a.len()
+            && is_low_surrogate(a.get(begin))
+            && (is_high_surrogate(unit_before(a, end)) || is_high_surrogate(unit_before(b, end)))
+        {
             begin += 1;
         }
         match begin < a.len() {
-            true => Some(unsafe { a.get_unchecked(begin..) }),
+            true => Some(get_unchecked!(a, begin..)),
             false => None,
         }
     }
@@ -106,11 +569,14 @@ impl Finder<str> for StringSuffix {
 pub struct GenericPrefix;
 impl<T: Eq> Finder<[T]> for GenericPrefix {
     fn common<'a>(a: &'a [T], b: &[T]) -> Option<&'a [T]> {
+        if !a.is_empty() && ptr::eq(a, b) {
+            return Some(a);
+        }
         let a_iter = a.iter();
         let b_iter = b.iter();
         let end = a_iter.zip(b_iter).count_eq();
         match end > 0 {
-            true => Some(unsafe { a.get_unchecked(..end) }),
+            true => Some(get_unchecked!(a, ..end)),
             false => None,
         }
     }
@@ -119,13 +585,247 @@ impl<T: Eq> Finder<[T]> for GenericPrefix {
 pub struct GenericSuffix;
 impl<T: Eq> Finder<[T]> for GenericSuffix {
     fn common<'a>(a: &'a [T], b: &[T]) -> Option<&'a [T]> {
+        if !a.is_empty() && ptr::eq(a, b) {
+            return Some(a);
+        }
         let a_iter = a.iter().rev();
         let b_iter = b.iter().rev();
         let end = a_iter.zip(b_iter).count_eq();
         let begin = a.len() - end;
         match begin < a.len() {
-            true => Some(unsafe { a.get_unchecked(begin..) }),
+            true => Some(get_unchecked!(a, begin..)),
             false => None,
         }
     }
 }
+
+/// Returns the run at the start of `a` for which `pred` holds against the
+/// corresponding elements of `b`. Unlike the other finders, this never
+/// assumes `pred` is an equivalence relation, so no chunking is attempted.
+pub fn prefix_while<'a, T, P>(a: &'a [T], b: &[T], pred: &P) -> Option<&'a [T]>
+where
+    P: Fn(&T, &T) -> bool,
+{
+    let end = a.iter().zip(b).take_while(|(a, b)| pred(a, b)).count();
+    match end > 0 {
+        true => Some(get_unchecked!(a, ..end)),
+        false => None,
+    }
+}
+
+/// Returns the run at the end of `a` for which `pred` holds against the
+/// corresponding elements of `b`. Unlike the other finders, this never
+/// assumes `pred` is an equivalence relation, so no chunking is attempted.
+pub fn suffix_while<'a, T, P>(a: &'a [T], b: &[T], pred: &P) -> Option<&'a [T]>
+where
+    P: Fn(&T, &T) -> bool,
+{
+    let a_iter = a.iter().rev();
+    let b_iter = b.iter().rev();
+    let end = a_iter.zip(b_iter).take_while(|(a, b)| pred(a, b)).count();
+    let begin = a.len() - end;
+    match begin < a.len() {
+        true => Some(get_unchecked!(a, begin..)),
+        false => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fmt;
+    use ya_rand::*;
+
+    /// Every pair of strings from a set of small variations on one another,
+    /// so the mismatch lands at every possible offset within a chunk.
+    fn pairs(len: u8) -> Vec<(Vec<u8>, Vec<u8>)> {
+        let base: Vec<u8> = (0..len).collect();
+        let mut pairs = Vec::new();
+        for i in 0..base.len() {
+            let mut changed = base.clone();
+            changed[i] ^= 0xff;
+            pairs.push((base.clone(), changed.clone()));
+            pairs.push((base[..i].to_vec(), base.clone()));
+            pairs.push((changed, base[i..].to_vec()));
+        }
+        pairs
+    }
+
+    fn check_chunk_size<const N: usize>() {
+        for (a, b) in pairs(200) {
+            let prefix = count_eq_prefix_bytes(&a, &b);
+            let suffix = count_eq_suffix_bytes(&a, &b);
+            assert_eq!(count_eq_prefix_chunked::<N, _>(&a, &b), prefix);
+            assert_eq!(count_eq_suffix_chunked::<N, _>(&a, &b), suffix);
+        }
+    }
+
+    /// Runs every finder over inputs hitting every offset, which is mainly
+    /// useful with `debug-checks` enabled to catch bad index math.
+    #[test]
+    fn finders() {
+        // Short pairs for the fused prefix and suffix loop.
+        for (a, b) in pairs(200).into_iter().chain(pairs(40)) {
+            let prefix = a.iter().zip(&b).count_eq();
+            let suffix = a.iter().rev().zip(b.iter().rev()).count_eq();
+            assert_eq!(BytePrefix::common(&a, &b).map_or(0, <[u8]>::len), prefix);
+            assert_eq!(ByteSuffix::common(&a, &b).map_or(0, <[u8]>::len), suffix);
+            assert_eq!(GenericPrefix::common(&a, &b).map_or(0, <[u8]>::len), prefix);
+            assert_eq!(GenericSuffix::common(&a, &b).map_or(0, <[u8]>::len), suffix);
+            assert_eq!(prefix_while(&a, &b, &u8::eq).map_or(0, <[u8]>::len), prefix);
+            assert_eq!(suffix_while(&a, &b, &u8::eq).map_or(0, <[u8]>::len), suffix);
+
+            // Turning the bytes into (mostly multi-byte) chars makes
+            // the boundary adjustment kick in.
+            let a: String = a.iter().map(|&b| char::from(b)).collect();
+            let b: String = b.iter().map(|&b| char::from(b)).collect();
+            let prefix = a.chars().zip(b.chars()).take_while(|(a, b)| a == b);
+            let prefix: usize = prefix.map(|(c, _)| c.len_utf8()).sum();
+            let suffix = a.chars().rev().zip(b.chars().rev());
+            let suffix: usize = suffix
+                .take_while(|(a, b)| a == b)
+                .map(|(c, _)| c.len_utf8())
+                .sum();
+            assert_eq!(StringPrefix::common(&a, &b).map_or(0, str::len), prefix);
+            assert_eq!(StringSuffix::common(&a, &b).map_or(0, str::len), suffix);
+            let (fused_prefix, fused_suffix) = prefix_and_suffix(&a, &b);
+            assert_eq!(fused_prefix.map_or(0, str::len), prefix);
+            assert_eq!(fused_suffix.map_or(0, str::len), suffix);
+        }
+    }
+
+    /// `a` is much longer than `b`, so only the end of `a` gets compared, and the cut
+    /// lands at every offset within one of its multi-byte chars. The result must be
+    /// sliced out of `a` itself, starting on a char boundary.
+    #[test]
+    fn suffix_long_truncation() {
+        let tail = "0123456789abcdefghijklmnopqrstuvwxyz_tail";
+        let a = format!("{}{tail}", "€".repeat(512));
+        for pad in 0..=6 {
+            let b = format!("{}{tail}", "x".repeat(pad));
+            let suffix = StringSuffix::common(&a, &b).unwrap();
+            assert_eq!(suffix, tail);
+            assert!(ptr::eq(suffix.as_ptr(), a[a.len() - tail.len()..].as_ptr()));
+            // With `b` padded by whole '€'s instead, all of `b` is shared.
+            let b = format!("{}{tail}", "€".repeat(pad));
+            let suffix = StringSuffix::common(&a, &b).unwrap();
+            assert_eq!(suffix.len(), b.len());
+            assert!(ptr::eq(suffix.as_ptr(), a[a.len() - b.len()..].as_ptr()));
+        }
+    }
+
+    #[test]
+    fn chunk_sizes() {
+        check_chunk_size::<1>();
+        check_chunk_size::<16>();
+        check_chunk_size::<32>();
+        check_chunk_size::<64>();
+        // Not a power of two, but nothing relies on that.
+        check_chunk_size::<24>();
+    }
+
+    /// Checks whichever comparison this build actually uses (chunked with the configured
+    /// chunk size, as vectors with `fastxfix_portable_simd`, unless `simd` is disabled)
+    /// against the byte-by-byte reference, on random inputs of random lengths sharing
+    /// random amounts of bytes.
+    #[test]
+    fn active_matches_bytes() {
+        let mut rng = new_rng();
+        for _ in 0..2000 {
+            let len = rng.bound(300) as usize;
+            let a: Vec<u8> = (0..len).map(|_| rng.bits(8) as u8).collect();
+            let shared = rng.bound(len as u64 + 1) as usize;
+            let extra = rng.bound(100) as usize;
+            let mut b = a[..shared].to_vec();
+            b.extend((0..extra).map(|_| rng.bits(8) as u8));
+            assert_eq!(count_eq_prefix(&a, &b), count_eq_prefix_bytes(&a, &b));
+            assert_eq!(count_eq_suffix(&a, &b), count_eq_suffix_bytes(&a, &b));
+            let mut b = a[len - shared..].to_vec();
+            b.splice(0..0, (0..extra).map(|_| rng.bits(8) as u8));
+            assert_eq!(count_eq_prefix(&a, &b), count_eq_prefix_bytes(&a, &b));
+            assert_eq!(count_eq_suffix(&a, &b), count_eq_suffix_bytes(&a, &b));
+        }
+    }
+
+    /// Returns the result of reducing `items` with `F` for every possible
+    /// binary tree shape, i.e. every way rayon could end up splitting them.
+    fn all_trees<'a, F, U>(items: &[&'a U]) -> Vec<Option<&'a U>>
+    where
+        F: Finder<U>,
+        U: ?Sized,
+    {
+        if let [item] = items {
+            return vec![Some(*item)];
+        }
+        let mut results = Vec::new();
+        for split in 1..items.len() {
+            for left in all_trees::<F, U>(&items[..split]) {
+                for right in all_trees::<F, U>(&items[split..]) {
+                    results.push(left.zip(right).and_then(|(l, r)| F::common(l, r)));
+                }
+            }
+        }
+        results
+    }
+
+    /// Asserts that every tree shape produces the same result
+    /// as a plain left-to-right fold.
+    fn check_trees<F, U>(items: &[&U])
+    where
+        F: Finder<U>,
+        U: ?Sized + PartialEq + fmt::Debug,
+    {
+        let expected = items[1..]
+            .iter()
+            .try_fold(items[0], |prev, current| F::common(prev, current));
+        for result in all_trees::<F, U>(items) {
+            assert_eq!(
+                result, expected,
+                "reduction isn't associative for {items:?}"
+            );
+        }
+    }
+
+    fn random_string(rng: &mut ShiroRng, len: usize) -> String {
+        const CHARS: [char; 4] = ['a', 'b', 'é', '😀'];
+        (0..len).map(|_| CHARS[rng.bits(2) as usize]).collect()
+    }
+
+    /// Every finder has to be associative, otherwise the result would depend
+    /// on how rayon happened to split the collection.
+    #[test]
+    fn associative() {
+        let mut rng = new_rng();
+        for _ in 0..200 {
+            // Cutting a shared random base at different points makes
+            // long, but different, common prefixes/suffixes likely.
+            let base = random_string(&mut rng, 40);
+            let mut prefixed = Vec::new();
+            let mut suffixed = Vec::new();
+            for _ in 0..6 {
+                let cut = 30 + rng.bits(4) as usize % 11;
+                let tail = random_string(&mut rng, 2);
+                prefixed.push(
+                    base.chars()
+                        .take(cut)
+                        .chain(tail.chars())
+                        .collect::<String>(),
+                );
+                let head = random_string(&mut rng, 2);
+                suffixed.push(head.chars().chain(base.chars().skip(40 - cut)).collect());
+            }
+            for strings in [prefixed, suffixed] {
+                let strs: Vec<&str> = strings.iter().map(String::as_str).collect();
+                let bytes: Vec<&[u8]> = strings.iter().map(String::as_bytes).collect();
+                for n in 2..=strs.len() {
+                    check_trees::<StringPrefix, _>(&strs[..n]);
+                    check_trees::<StringSuffix, _>(&strs[..n]);
+                    check_trees::<BytePrefix, _>(&bytes[..n]);
+                    check_trees::<ByteSuffix, _>(&bytes[..n]);
+                    check_trees::<GenericPrefix, _>(&bytes[..n]);
+                    check_trees::<GenericSuffix, _>(&bytes[..n]);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,125 @@
+/*!
+Contains the [`CommonFloat`] trait, which allows finding the longest common prefix/suffix
+of collections of floating point slices.
+
+Floats can't use [`CommonRaw`](crate::CommonRaw) since they aren't `Eq`, so instead they get
+reinterpreted as slices of their bit patterns (`u32` for `f32`, `u64` for `f64`) using
+[`bytemuck`], and those are compared. This means the comparison is *bitwise*, not numeric:
+
+- `0.0` and `-0.0` are considered different.
+- A `NaN` is equal to any other `NaN` with the exact same bit pattern, and different from
+  `NaN`s with a different sign or payload.
+
+This gives a total, deterministic comparison without needing any kind of epsilon.
+*/
+
+use crate::finder::*;
+use crate::reduce_common;
+use bytemuck::{Pod, cast_slice};
+use rayon::prelude::*;
+
+mod private {
+    pub trait Sealed {}
+    impl Sealed for f32 {}
+    impl Sealed for f64 {}
+}
+
+/// Floating point types which can be compared by their bit patterns.
+///
+/// This trait is sealed and implemented for [`f32`] and [`f64`].
+pub trait Float: private::Sealed + Pod + Sync {
+    /// Unsigned integer with the same size as the float.
+    type Bits: Pod + Eq + Sync;
+}
+
+impl Float for f32 {
+    type Bits = u32;
+}
+
+impl Float for f64 {
+    type Bits = u64;
+}
+
+/// Trait for finding the longest common prefix/suffix of any 2D collection of floats,
+/// comparing them bitwise.
+pub trait CommonFloat<F: Float> {
+    /// Returns the longest common prefix of all referenced floats.
+    ///
+    /// Returns `None` when there is no common prefix.
+    fn common_prefix_float(&self) -> Option<Vec<F>> {
+        self.common_prefix_float_ref().map(|s| s.to_vec())
+    }
+
+    /// Returns the longest common suffix of all referenced floats.
+    ///
+    /// Returns `None` when there is no common suffix.
+    fn common_suffix_float(&self) -> Option<Vec<F>> {
+        self.common_suffix_float_ref().map(|s| s.to_vec())
+    }
+
+    /// Returns a reference to the element which has the longest common
+    /// prefix of all floats in the collection.
+    ///
+    /// Returns `None` when there is no common prefix.
+    fn common_prefix_float_ref(&self) -> Option<&[F]>;
+
+    /// Returns a reference to the element which has the longest common
+    /// suffix of all floats in the collection.
+    ///
+    /// Returns `None` when there is no common suffix.
+    fn common_suffix_float_ref(&self) -> Option<&[F]>;
+}
+
+impl<C: ?Sized, T, F> CommonFloat<F> for C
+where
+    for<'a> &'a C: IntoParallelIterator<Item = &'a T>,
+    T: AsRef<[F]> + Sync,
+    F: Float,
+{
+    fn common_prefix_float_ref(&self) -> Option<&[F]> {
+        let iter = self
+            .into_par_iter()
+            .map(|s| cast_slice::<F, F::Bits>(s.as_ref()));
+        reduce_common::<GenericPrefix, _, _>(iter).map(cast_slice)
+    }
+
+    fn common_suffix_float_ref(&self) -> Option<&[F]> {
+        let iter = self
+            .into_par_iter()
+            .map(|s| cast_slice::<F, F::Bits>(s.as_ref()));
+        reduce_common::<GenericSuffix, _, _>(iter).map(cast_slice)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CommonFloat;
+
+    #[test]
+    fn signed_zero() {
+        let input = vec![vec![1.5f32, 0.0, 2.0], vec![1.5, -0.0, 2.0]];
+        assert_eq!(input.common_prefix_float().unwrap(), [1.5]);
+        assert_eq!(input.common_suffix_float().unwrap(), [2.0]);
+
+        let input = vec![vec![0.0f64, 0.0], vec![0.0, 0.0, 0.0]];
+        assert_eq!(input.common_prefix_float().unwrap(), [0.0, 0.0]);
+        let input = vec![vec![-0.0f64], vec![0.0]];
+        assert!(input.common_prefix_float().is_none());
+    }
+
+    #[test]
+    fn nan() {
+        let other_nan = f64::from_bits(f64::NAN.to_bits() | 1);
+        let input = vec![vec![f64::NAN, 1.0, 2.0], vec![f64::NAN, 1.0, 3.0]];
+        let prefix = input.common_prefix_float_ref().unwrap();
+        assert_eq!(prefix.len(), 2);
+        assert!(prefix[0].is_nan());
+
+        let input = vec![vec![f64::NAN, 1.0], vec![other_nan, 1.0]];
+        assert!(input.common_prefix_float_ref().is_none());
+        assert_eq!(input.common_suffix_float().unwrap(), [1.0]);
+
+        let input = vec![vec![1.0f32, f32::NAN], vec![-f32::NAN]];
+        assert!(input.common_suffix_float_ref().is_none());
+    }
+}
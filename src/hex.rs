@@ -0,0 +1,37 @@
+/*!
+Contains [`HexSlice`], a small wrapper for displaying binary data (like the result of
+[`CommonRaw::common_prefix_raw_ref`](crate::CommonRaw::common_prefix_raw_ref) on a collection
+of byte buffers) in a readable form.
+*/
+
+use std::fmt;
+
+/// Wrapper whose [`Display`](fmt::Display) impl prints every byte as two lowercase
+/// hex digits, separated by a single space (e.g. `0a ff 1c`).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct HexSlice<'a>(pub &'a [u8]);
+
+impl fmt::Display for HexSlice<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut iter = self.0.iter();
+        if let Some(first) = iter.next() {
+            write!(f, "{first:02x}")?;
+            for byte in iter {
+                write!(f, " {byte:02x}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::HexSlice;
+
+    #[test]
+    fn display() {
+        assert_eq!(HexSlice(&[0x0a, 0xff, 0x1c]).to_string(), "0a ff 1c");
+        assert_eq!(HexSlice(&[0]).to_string(), "00");
+        assert_eq!(HexSlice(&[]).to_string(), "");
+    }
+}
@@ -0,0 +1,119 @@
+/*!
+Contains [`PrefixInterner`], which deduplicates common prefixes/suffixes found across
+separate queries, so identical results share storage and can be compared by pointer.
+*/
+
+use crate::CommonStr;
+use std::collections::HashSet;
+use std::sync::Arc;
+
+/// Set of previously found prefixes/suffixes, each handed out as a shared [`Arc<str>`].
+///
+/// Results are looked up by reference before being inserted, so a result which was
+/// already interned doesn't cause any allocation.
+///
+/// ```
+/// use fastxfix::PrefixInterner;
+/// use std::sync::Arc;
+///
+/// let mut interner = PrefixInterner::new();
+/// let a = interner.common_prefix(&["GET /a", "GET /b"]).unwrap();
+/// let b = interner.common_prefix(&["GET /x", "GET /y"]).unwrap();
+/// assert!(Arc::ptr_eq(&a, &b));
+/// assert_eq!(interner.len(), 1);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct PrefixInterner {
+    set: HashSet<Arc<str>>,
+}
+
+impl PrefixInterner {
+    /// Creates an empty interner.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the interned copy of `s`, inserting it if it isn't present yet.
+    pub fn intern(&mut self, s: &str) -> Arc<str> {
+        if let Some(interned) = self.set.get(s) {
+            return Arc::clone(interned);
+        }
+        let interned: Arc<str> = s.into();
+        self.set.insert(Arc::clone(&interned));
+        interned
+    }
+
+    /// Finds the longest common prefix of `collection` and returns its interned copy.
+    ///
+    /// Returns `None`, leaving the interner untouched, when there is no common prefix.
+    pub fn common_prefix<C: CommonStr + ?Sized>(&mut self, collection: &C) -> Option<Arc<str>> {
+        collection.common_prefix_ref().map(|s| self.intern(s))
+    }
+
+    /// Finds the longest common suffix of `collection` and returns its interned copy.
+    ///
+    /// Returns `None`, leaving the interner untouched, when there is no common suffix.
+    pub fn common_suffix<C: CommonStr + ?Sized>(&mut self, collection: &C) -> Option<Arc<str>> {
+        collection.common_suffix_ref().map(|s| self.intern(s))
+    }
+
+    /// Returns whether `s` has been interned.
+    pub fn contains(&self, s: &str) -> bool {
+        self.set.contains(s)
+    }
+
+    /// Returns the number of distinct interned strings.
+    pub fn len(&self) -> usize {
+        self.set.len()
+    }
+
+    /// Returns whether nothing has been interned.
+    pub fn is_empty(&self) -> bool {
+        self.set.is_empty()
+    }
+
+    /// Returns an iterator over every interned string, in arbitrary order.
+    pub fn iter(&self) -> impl Iterator<Item = &Arc<str>> {
+        self.set.iter()
+    }
+
+    /// Removes every interned string. Previously returned [`Arc`]s stay valid,
+    /// but won't be shared with anything interned afterwards.
+    pub fn clear(&mut self) {
+        self.set.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PrefixInterner;
+    use std::sync::Arc;
+
+    #[test]
+    fn dedup() {
+        let mut interner = PrefixInterner::new();
+        assert!(interner.is_empty());
+        let batch_a = vec!["log_2024_a".to_string(), "log_2024_b".into()];
+        let batch_b = ["log_2024_x", "log_2024_y", "log_2024_z"];
+        let a = interner.common_prefix(&batch_a).unwrap();
+        let b = interner.common_prefix(&batch_b).unwrap();
+        assert_eq!(&*a, "log_2024_");
+        assert!(Arc::ptr_eq(&a, &b));
+
+        let suffix = interner.common_suffix(&["a_2024_", "b_2024_"]).unwrap();
+        assert_eq!(&*suffix, "_2024_");
+        assert!(!Arc::ptr_eq(&a, &suffix));
+        assert_eq!(interner.len(), 2);
+        assert!(interner.contains("_2024_"));
+
+        assert_eq!(interner.common_prefix(&["x", "y"]), None);
+        let empty: [&str; 0] = [];
+        assert_eq!(interner.common_suffix(&empty), None);
+        assert_eq!(interner.len(), 2);
+
+        assert!(Arc::ptr_eq(&interner.intern("log_2024_"), &a));
+        interner.clear();
+        assert!(!Arc::ptr_eq(&interner.intern("log_2024_"), &a));
+        assert_eq!(interner.iter().count(), 1);
+    }
+}
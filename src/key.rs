@@ -0,0 +1,102 @@
+/*!
+Contains [`PrefixKey`], a sort key ordering collections by how much their strings have in common.
+*/
+
+use std::cmp::Ordering;
+
+/// The common prefix of a collection, ordered by its length first and its contents second.
+///
+/// Obtained through [`CommonStr::prefix_key`](crate::CommonStr::prefix_key), to sort
+/// collections by how homogeneous they are. Collections without a common prefix
+/// (including empty ones) sort before all others.
+///
+/// Computing a key finds the common prefix of the whole collection, so prefer
+/// [`slice::sort_by_cached_key`] over [`slice::sort_by_key`], which recomputes
+/// keys for every comparison.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct PrefixKey {
+    prefix: Option<String>,
+}
+
+impl PrefixKey {
+    /// Returns the common prefix this key was made from, if there was one.
+    pub fn prefix(&self) -> Option<&str> {
+        self.prefix.as_deref()
+    }
+
+    /// Returns the length of the common prefix in bytes, which is 0 when there was none.
+    pub fn len(&self) -> usize {
+        self.prefix.as_ref().map_or(0, String::len)
+    }
+
+    /// Returns `true` when there was no common prefix.
+    pub fn is_empty(&self) -> bool {
+        self.prefix.is_none()
+    }
+
+    /// Returns the common prefix this key was made from, if there was one.
+    pub fn into_prefix(self) -> Option<String> {
+        self.prefix
+    }
+}
+
+/// An empty prefix is the same as having no common prefix.
+impl From<Option<String>> for PrefixKey {
+    fn from(prefix: Option<String>) -> Self {
+        Self {
+            prefix: prefix.filter(|p| !p.is_empty()),
+        }
+    }
+}
+
+impl Ord for PrefixKey {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // A missing prefix has length 0, which no actual prefix has,
+        // and strings are compared by their bytes.
+        self.len()
+            .cmp(&other.len())
+            .then_with(|| self.prefix().cmp(&other.prefix()))
+    }
+}
+
+impl PartialOrd for PrefixKey {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PrefixKey;
+    use crate::CommonStr;
+
+    #[test]
+    fn ordering() {
+        let mut collections = [
+            vec!["abc1", "abc2"],
+            vec!["x", "y"],
+            vec!["ab1", "ab2"],
+            vec!["zz1", "zz2"],
+            vec![],
+            vec!["abd1", "abd2"],
+        ];
+        collections.sort_by_cached_key(|c| c.prefix_key());
+        let prefixes: Vec<_> = collections
+            .iter()
+            .map(|c| c.prefix_key().into_prefix())
+            .collect();
+        let expected = [None, None, Some("ab"), Some("zz"), Some("abc"), Some("abd")];
+        assert_eq!(prefixes, expected.map(|p| p.map(String::from)));
+
+        let none = [""; 0].prefix_key();
+        assert!(none.is_empty());
+        assert_eq!(none.len(), 0);
+        assert!(none < ["a"].prefix_key());
+        assert_eq!(["ab", "ac"].prefix_key(), ["a"].prefix_key());
+        // A single empty string has no common prefix, same as an empty collection.
+        let empty = [""].prefix_key();
+        assert!(empty.is_empty());
+        assert_eq!(empty, none);
+        assert_eq!(PrefixKey::from(Some(String::new())), none);
+    }
+}
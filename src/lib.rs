@@ -13,9 +13,64 @@ access to the methods of [`CommonRaw`]. These two conditions are not mutually ex
 it's up to the user to ensure they're using the method that best coincides with what they're
 trying to accomplish.
 
+Element types don't need to be owned by the standard library: anything that can be referenced
+as `str` (`String`, `Box<str>`, `Arc<str>`, `Cow<str>`, ...) or as `[U]` (`Vec<U>`, `Box<[U]>`,
+`Arc<[U]>`, or inline-storage types like `SmallVec<[U; N]>` and `ArrayVec<U, N>`) works,
+as long as it's `Sync`. Boxed trait objects (`Box<dyn AsRef<str> + Sync>`) are the one
+exception, since boxes don't forward `AsRef` to what they hold: wrap them in [`DynStr`].
+
+Custom collections work too, as long as a shared reference to one implements rayon's
+`IntoParallelIterator`, yielding references to its elements. Rayon then provides `par_iter`
+for it through `IntoParallelRefIterator`, which can't be implemented directly because of that.
+
+Collections of slices borrowed from a non-`'static` buffer (like `Vec<&[u8]>` pointing into a
+memory-mapped file) are the exception: use [`CommonRawBorrowed`] for those, which also returns results
+borrowing from the buffer itself rather than from the collection. [`CommonStrBorrowed`] does the
+same for `Vec<&str>` and friends.
+Similarly, `VecDeque<U>` elements aren't contiguous and can't be referenced as slices,
+so they're handled by [`CommonDeque`] instead.
+
 If you're trying to extract information about strings, **always** prefer using [`CommonStr`]
 methods: they are specifically optimized for handling rust's UTF-8 encoded strings.
 
+## Features
+
+- `simd` (enabled by default): compares string bytes in chunks sized to fit a 128-bit
+  vector register, allowing the compiler to autovectorize the comparisons. Disabling it
+  falls back to plain byte-by-byte comparisons.
+- `bytemuck`: enables [`CommonFloat`], which finds common prefixes/suffixes of `f32`/`f64`
+  slices by comparing their bit patterns.
+- `debug-checks`: replaces every unchecked index/length operation with a checked one, which
+  panics instead of causing UB if the crate ever computes a bad index. Meant for test suites.
+- `profiling`: counts chunk/byte comparisons and pairwise comparisons of elements, exposed
+  through `common_prefix_profiled` as [`FinderStats`]. This adds atomic operations to the hot loops,
+  so leave it off outside of tuning.
+- `bytes`: enables [`CommonBytes`], which returns common prefixes/suffixes of `bytes::Bytes`
+  collections as zero-copy slices of the original buffers.
+- `bumpalo`: enables `common_prefix_in`/`common_suffix_in`, which copy their result into a
+  `bumpalo::Bump` arena instead of allocating a `String`.
+- `arrayvec`: enables `common_prefix_raw_inline`/`common_suffix_raw_inline`, which copy
+  their result into an `arrayvec::ArrayVec` instead of allocating a `Vec`.
+- `unicode-normalization`: enables [`CommonNormalized`], which treats canonically equivalent strings
+  (like a precomposed `é` and `e` followed by a combining accent) as equal. Much slower
+  than the byte-level comparisons, so only use it for data that needs it.
+  It can also compare bytes as usual while never keeping a base char whose combining marks
+  got cut off, which is much cheaper.
+- `regex`: enables `common_prefix_regex`/`common_suffix_regex`, which return a `regex::Regex`
+  anchored on the (escaped) common prefix/suffix.
+- `futures`: enables `common_prefix_stream`/`common_suffix_stream`, which shrink a running
+  result as strings arrive through a `futures::Stream`.
+- `serde`: implements `Serialize`/`Deserialize` for [`Partial`], so shards of a distributed
+  search can send their partial results to whatever merges them.
+
+The chunk size used by `simd` defaults to 16 bytes. To benchmark other sizes on your own
+hardware, build with `RUSTFLAGS='--cfg fastxfix_chunk_size="N"'`, where `N` is 16, 32, or 64.
+
+On nightly, building with `RUSTFLAGS='--cfg fastxfix_portable_simd'` makes `simd` compare
+the chunks as explicit `std::simd` vectors, which also pinpoints the mismatching byte within
+a chunk through a bitmask. It's a `--cfg` rather than a feature so `--all-features` keeps
+building on stable.
+
 ## Examples
 
 ```
@@ -34,15 +89,94 @@ assert!(v.common_suffix_len().is_none());
 ```
 */
 
+#![cfg_attr(all(feature = "simd", fastxfix_portable_simd), feature(portable_simd))]
 #![deny(missing_docs)]
 
+mod bits;
+mod borrowed;
+mod cancel;
+mod constant;
+mod deque;
+mod display;
+mod dynamic;
+mod ffi;
 mod finder;
+#[cfg(feature = "bytemuck")]
+mod float;
+mod hex;
+mod interner;
+mod key;
+mod memo;
+mod merge;
+mod nested;
+#[cfg(feature = "unicode-normalization")]
+mod normalized;
+mod pairs;
+mod prepared;
+#[cfg(feature = "profiling")]
+mod profiling;
+mod query;
+mod seq;
+#[cfg(feature = "bytes")]
+mod shared;
+#[cfg(feature = "futures")]
+mod stream;
+mod strip;
+mod typed;
+mod utf16;
 
+#[cfg(feature = "arrayvec")]
+use arrayvec::{ArrayVec, CapacityError};
+pub use bits::*;
+pub use borrowed::*;
+#[cfg(feature = "bumpalo")]
+use bumpalo::Bump;
+pub use cancel::*;
+pub use constant::*;
+pub use deque::*;
+pub use display::*;
+pub use dynamic::*;
+pub use ffi::*;
 use finder::*;
+#[cfg(feature = "bytemuck")]
+pub use float::*;
+pub use hex::*;
+pub use interner::*;
+pub use key::*;
+pub use memo::*;
+pub use merge::*;
+pub use nested::*;
+#[cfg(feature = "unicode-normalization")]
+pub use normalized::*;
+pub use pairs::*;
+pub use prepared::*;
+#[cfg(feature = "profiling")]
+pub use profiling::FinderStats;
+pub use query::*;
+use rayon::ThreadPool;
 use rayon::prelude::*;
+#[cfg(feature = "regex")]
+use regex::Regex;
+pub use seq::*;
+#[cfg(feature = "bytes")]
+pub use shared::*;
+use std::cmp::{Ordering, Reverse};
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
 use std::num::NonZeroUsize;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering as AtomicOrdering};
+use std::{slice, str};
+#[cfg(feature = "futures")]
+pub use stream::*;
+pub use strip::*;
+pub use typed::*;
+pub use utf16::*;
 
 /// Trait for finding the longest common [`String`] prefix/suffix of any 2D collection.
+///
+/// Every method hands the collection to rayon, whose setup costs far more than comparing
+/// a couple of strings does. To compare exactly two strings, [`common_prefix_endpoints`]
+/// and [`common_prefix_and_suffix_pair`] skip it entirely.
 pub trait CommonStr {
     /// Returns the longest common prefix of all referenced strings.
     ///
@@ -62,29 +196,593 @@ pub trait CommonStr {
     ///
     /// Returns `None` instead of 0 when there is no common prefix.
     fn common_prefix_len(&self) -> Option<NonZeroUsize> {
-        self.common_prefix_ref()
-            .map(|s| unsafe { NonZeroUsize::new_unchecked(s.len()) })
+        self.common_prefix_ref().map(|s| nonzero_len(s.len()))
     }
 
     /// Returns the length of the longest common suffix of all referenced strings.
     ///
     /// Returns `None` instead of 0 when there is no common suffix.
     fn common_suffix_len(&self) -> Option<NonZeroUsize> {
-        self.common_suffix_ref()
-            .map(|s| unsafe { NonZeroUsize::new_unchecked(s.len()) })
+        self.common_suffix_ref().map(|s| nonzero_len(s.len()))
+    }
+
+    /// Same as [`CommonStr::common_prefix_len`], but returns 0
+    /// when there is no common prefix.
+    fn common_prefix_len_or_zero(&self) -> usize {
+        self.common_prefix_len().map_or(0, NonZeroUsize::get)
+    }
+
+    /// Same as [`CommonStr::common_suffix_len`], but returns 0
+    /// when there is no common suffix.
+    fn common_suffix_len_or_zero(&self) -> usize {
+        self.common_suffix_len().map_or(0, NonZeroUsize::get)
+    }
+
+    /// Same as [`CommonStr::common_prefix_ref_indexed`], but returns the result as
+    /// a [`PrefixRef`], which carries the index of the element it borrows from.
+    ///
+    /// Only available for collections which can be split at arbitrary indices.
+    fn common_prefix_typed(&self) -> Option<PrefixRef<'_>>
+    where
+        for<'a> &'a Self: IntoParallelIterator<Iter: IndexedParallelIterator>,
+    {
+        self.common_prefix_ref_indexed()
+            .map(|(index, prefix)| PrefixRef::new(prefix, index))
+    }
+
+    /// Returns the longest common prefix of all referenced strings,
+    /// copied into `arena` instead of a heap allocated [`String`].
+    ///
+    /// Returns `None` when there is no common prefix.
+    #[cfg(feature = "bumpalo")]
+    fn common_prefix_in<'a>(&self, arena: &'a Bump) -> Option<&'a str> {
+        self.common_prefix_ref().map(|s| &*arena.alloc_str(s))
+    }
+
+    /// Returns the longest common suffix of all referenced strings,
+    /// copied into `arena` instead of a heap allocated [`String`].
+    ///
+    /// Returns `None` when there is no common suffix.
+    #[cfg(feature = "bumpalo")]
+    fn common_suffix_in<'a>(&self, arena: &'a Bump) -> Option<&'a str> {
+        self.common_suffix_ref().map(|s| &*arena.alloc_str(s))
+    }
+
+    /// Returns a [`Regex`] matching strings which start with the longest
+    /// common prefix of all referenced strings.
+    ///
+    /// The prefix is escaped, so any regex metacharacters it contains are
+    /// matched literally.
+    ///
+    /// Returns `None` when there is no common prefix.
+    #[cfg(feature = "regex")]
+    fn common_prefix_regex(&self) -> Option<Regex> {
+        let prefix = self.common_prefix_ref()?;
+        let pattern = format!("^{}", regex::escape(prefix));
+        Some(Regex::new(&pattern).expect("escaped prefix is a valid regex"))
+    }
+
+    /// Returns a [`Regex`] matching strings which end with the longest
+    /// common suffix of all referenced strings.
+    ///
+    /// The suffix is escaped, so any regex metacharacters it contains are
+    /// matched literally.
+    ///
+    /// Returns `None` when there is no common suffix.
+    #[cfg(feature = "regex")]
+    fn common_suffix_regex(&self) -> Option<Regex> {
+        let suffix = self.common_suffix_ref()?;
+        let pattern = format!("{}$", regex::escape(suffix));
+        Some(Regex::new(&pattern).expect("escaped suffix is a valid regex"))
+    }
+
+    /// Same as [`CommonStr::common_prefix_ref`], but also returns the total amount
+    /// of bytes compared across every comparison of two strings.
+    ///
+    /// This counts the logical work (each equal byte, plus the one which differed),
+    /// no matter how many bytes were compared at once, to feed cost models or spot
+    /// pathological inputs. The counter is only updated by this method, so nothing else
+    /// pays for it. The amount depends on how rayon splits the collection, and the query
+    /// stops early once nothing is left in common, so it can vary between runs.
+    fn common_prefix_with_cost(&self) -> (Option<&str>, u64);
+
+    /// Same as [`CommonStr::common_prefix_ref`], but also returns [`FinderStats`]
+    /// describing how much work finding the prefix took.
+    ///
+    /// The stats are gathered from process-wide counters, so any queries running
+    /// concurrently with this one get included in them.
+    #[cfg(feature = "profiling")]
+    fn common_prefix_profiled(&self) -> (Option<&str>, FinderStats) {
+        let before = FinderStats::snapshot();
+        let prefix = self.common_prefix_ref();
+        (prefix, FinderStats::snapshot().since(before))
+    }
+
+    /// Returns the amount of bytes in the longest common prefix of all
+    /// referenced strings, *before* it gets adjusted to lie on a char boundary.
+    ///
+    /// For pure ASCII input this is always equal to [`CommonStr::common_prefix_len`],
+    /// but when the strings diverge in the middle of a multi-byte char the count
+    /// will include the leading bytes of that char, making it larger. This means
+    /// the value can't be used to slice any of the strings, but is perfectly
+    /// fine for consumers who only care about the underlying bytes.
+    ///
+    /// Returns `None` instead of 0 when there are no common prefix bytes.
+    fn common_prefix_raw_byte_len(&self) -> Option<NonZeroUsize> {
+        self.common_prefix_bytes_unchecked()
+            .map(|s| nonzero_len(s.len()))
+    }
+
+    /// Returns the amount of bytes in the longest common suffix of all
+    /// referenced strings, *before* it gets adjusted to lie on a char boundary.
+    ///
+    /// For pure ASCII input this is always equal to [`CommonStr::common_suffix_len`],
+    /// but when the strings diverge in the middle of a multi-byte char the count
+    /// will include the trailing bytes of that char, making it larger. This means
+    /// the value can't be used to slice any of the strings, but is perfectly
+    /// fine for consumers who only care about the underlying bytes.
+    ///
+    /// Returns `None` instead of 0 when there are no common suffix bytes.
+    fn common_suffix_raw_byte_len(&self) -> Option<NonZeroUsize> {
+        self.common_suffix_bytes_unchecked()
+            .map(|s| nonzero_len(s.len()))
+    }
+
+    /// Returns an iterator over the chars of the longest common prefix
+    /// of all referenced strings, without allocating.
+    ///
+    /// Returns `None` when there is no common prefix.
+    fn common_prefix_chars(&self) -> Option<str::Chars<'_>> {
+        self.common_prefix_ref().map(str::chars)
+    }
+
+    /// Returns an iterator over the chars of the longest common suffix
+    /// of all referenced strings, without allocating.
+    ///
+    /// Returns `None` when there is no common suffix.
+    fn common_suffix_chars(&self) -> Option<str::Chars<'_>> {
+        self.common_suffix_ref().map(str::chars)
+    }
+
+    /// Same as [`CommonStr::common_prefix`], but executes within `pool`
+    /// instead of rayon's global thread pool.
+    ///
+    /// This is done using [`ThreadPool::install`], so calling it from a thread
+    /// which already belongs to some pool behaves exactly as rayon specifies there.
+    fn common_prefix_in_pool(&self, pool: &ThreadPool) -> Option<String>
+    where
+        Self: Sync,
+    {
+        pool.install(|| self.common_prefix())
+    }
+
+    /// Same as [`CommonStr::common_suffix`], but executes within `pool`
+    /// instead of rayon's global thread pool.
+    ///
+    /// This is done using [`ThreadPool::install`], so calling it from a thread
+    /// which already belongs to some pool behaves exactly as rayon specifies there.
+    fn common_suffix_in_pool(&self, pool: &ThreadPool) -> Option<String>
+    where
+        Self: Sync,
+    {
+        pool.install(|| self.common_suffix())
+    }
+
+    /// Returns the longest common prefix of all referenced strings as a [`PrefixKey`],
+    /// for sorting collections by how much their strings have in common.
+    fn prefix_key(&self) -> PrefixKey {
+        self.common_prefix().into()
     }
 
     /// Returns a reference to the string which has the longest common
     /// prefix of all strings in the collection.
     ///
+    /// The result always borrows from the first string the collection yields: each
+    /// pair of strings keeps borrowing from the left one, and rayon keeps them in order.
+    /// So for collections with a fixed order, it's the same element on every run,
+    /// no matter how the work gets scheduled.
+    ///
     /// Returns `None` when there is no common prefix.
     fn common_prefix_ref(&self) -> Option<&str>;
 
     /// Returns a reference to the string which has the longest common
     /// suffix of all strings in the collection.
     ///
+    /// Like [`CommonStr::common_prefix_ref`], the result always borrows from
+    /// the first string the collection yields.
+    ///
     /// Returns `None` when there is no common suffix.
     fn common_suffix_ref(&self) -> Option<&str>;
+
+    /// Returns the longest common prefix of all referenced strings, assuming
+    /// they're all ASCII.
+    ///
+    /// Every byte of an ASCII string is a char boundary, so this skips moving the
+    /// length of the common bytes onto one. Only worth it when the strings are already
+    /// known to be ASCII: checking them with [`str::is_ascii`] first costs more than it saves.
+    ///
+    /// Returns `None` when there is no common prefix.
+    ///
+    /// # Safety
+    ///
+    /// Every string in the collection must be ASCII. Debug builds panic when one isn't,
+    /// but release builds might return a string ending in the middle of a char.
+    unsafe fn common_prefix_ascii(&self) -> Option<String>;
+
+    /// Returns the bytes of the string which has the longest common prefix of all
+    /// strings in the collection, limited to the longest common prefix of their bytes.
+    ///
+    /// Unlike [`CommonStr::common_prefix_ref`], the result *isn't* adjusted to lie on
+    /// a char boundary. When the strings diverge in the middle of a multi-byte char,
+    /// the result ends with an incomplete char, making it invalid UTF-8. This is why
+    /// it's returned as `&[u8]` instead of `&str`.
+    ///
+    /// Returns `None` when there are no common prefix bytes.
+    fn common_prefix_bytes_unchecked(&self) -> Option<&[u8]>;
+
+    /// Returns the bytes of the string which has the longest common suffix of all
+    /// strings in the collection, limited to the longest common suffix of their bytes.
+    ///
+    /// Unlike [`CommonStr::common_suffix_ref`], the result *isn't* adjusted to lie on
+    /// a char boundary. When the strings diverge in the middle of a multi-byte char,
+    /// the result begins with an incomplete char, making it invalid UTF-8. This is why
+    /// it's returned as `&[u8]` instead of `&str`.
+    ///
+    /// Returns `None` when there are no common suffix bytes.
+    fn common_suffix_bytes_unchecked(&self) -> Option<&[u8]>;
+
+    /// Returns, for every string in the collection, the length of the prefix it
+    /// shares with the first string in the collection (the point where it diverges).
+    ///
+    /// The first string always yields its own length, and the length of the
+    /// longest common prefix is the smallest value in the returned `Vec`.
+    /// Lengths are in bytes and always lie on a char boundary.
+    ///
+    /// Results are in iteration order, so collections without a defined order
+    /// (like `HashSet`) will use an arbitrary string as the reference.
+    fn prefix_divergence(&self) -> Vec<usize>;
+
+    /// Returns a reference to the first string in the collection, limited to
+    /// the longest common prefix of all strings in the collection.
+    ///
+    /// **The collection must be sorted lexicographically.** When it is, the common
+    /// prefix of the first and last strings is the common prefix of every string, so
+    /// only those two get compared. This takes O(L) time instead of O(N * L).
+    ///
+    /// The result is unspecified when the collection isn't sorted. In debug builds
+    /// this is checked for, causing a panic when violated.
+    ///
+    /// Returns `None` when there is no common prefix.
+    fn common_prefix_sorted(&self) -> Option<&str>;
+
+    /// Returns the lexicographically smallest and largest strings of the
+    /// collection, found in a single parallel pass.
+    ///
+    /// Every string lies between these two, so passing them to
+    /// [`common_prefix_endpoints`] gives the common prefix of the whole
+    /// collection in O(N + L) time, without requiring it to be sorted.
+    ///
+    /// Returns `None` when the collection is empty.
+    fn lexicographic_bounds(&self) -> Option<(&str, &str)>;
+
+    /// Same as [`CommonStr::common_prefix_ref`], but first checks whether the
+    /// shortest string is a prefix of every other string, in which case that
+    /// string is the result.
+    ///
+    /// The check is a plain [`str::starts_with`] against every string, which is
+    /// cheaper than finding where each pair diverges. This pays off for hierarchical
+    /// data (like paths which all lie beneath one of them), but costs an extra pass
+    /// whenever the check fails and the full search has to run anyway.
+    fn common_prefix_shortest_first(&self) -> Option<&str>;
+
+    /// Same as [`CommonStr::common_prefix`], but first collects the distinct
+    /// strings into a [`HashSet`], then only compares those.
+    ///
+    /// **This is usually slower.** Hashing reads every byte of every string, while
+    /// comparing stops at the end of the common prefix, so the set costs more than
+    /// the comparisons it saves. In the `dedup` benchmark it takes about 3x as long
+    /// with only 100 distinct strings, and about 5x as long with no duplicates at all.
+    /// Measure on your own data before reaching for it.
+    /// Duplicates sharing a single allocation (like clones of an `Arc<str>`) are
+    /// already cheap for [`CommonStr::common_prefix`], since they're compared by pointer.
+    fn common_prefix_dedup(&self) -> Option<String>;
+
+    /// Returns a reference to the longest common prefix of only the `k` longest
+    /// strings, ignoring shorter ones entirely.
+    ///
+    /// The strings are selected in O(N) time without sorting the whole collection.
+    /// Strings tied in length with the `k`th longest are picked arbitrarily.
+    /// When `k` is at least the length of the collection, every string is used.
+    ///
+    /// Returns `None` when there is no common prefix, including when `k` is 0.
+    fn common_prefix_of_longest(&self, k: usize) -> Option<&str>;
+
+    /// Returns, for each of the `thresholds`, the longest prefix shared by at least
+    /// that fraction of the strings, in the same order as the `thresholds`.
+    ///
+    /// A threshold of `0.9` gives the longest prefix which at least 90% of the strings
+    /// (rounded up) start with, and a threshold of `1.0` gives the common prefix of all of
+    /// them. Thresholds requiring fewer than one string are treated as requiring one, which
+    /// gives the longest string. When several different prefixes are equally long, the
+    /// lexicographically smallest one is returned.
+    ///
+    /// The strings are sorted once (by reference), after which strings sharing any prefix
+    /// are adjacent, so every threshold only takes a single pass comparing the first and
+    /// last strings of each run of that many strings.
+    ///
+    /// A threshold gets `None` when no prefix is shared by enough strings, which
+    /// includes thresholds above `1.0` and empty collections.
+    fn prefix_coverage_tiers(&self, thresholds: &[f64]) -> Vec<(f64, Option<String>)>;
+
+    /// Returns a parallel iterator over what remains of every string after
+    /// removing the longest common prefix, borrowing from the originals.
+    ///
+    /// The prefix is found once up front, and the remainders are only sliced
+    /// as the iterator is driven, so they can be fed into further rayon
+    /// processing without collecting them first. Driving the iterator panics
+    /// if a string can no longer be sliced where the prefix ended, which only
+    /// happens when its `as_ref` returns something different the second time.
+    ///
+    /// Returns `None` when there is no common prefix.
+    fn prefix_stripped_iter(&self) -> Option<impl ParallelIterator<Item = &str>>;
+
+    /// Returns a parallel iterator over what remains of every string after
+    /// removing the longest common suffix, borrowing from the originals.
+    ///
+    /// The suffix is found once up front, and the remainders are only sliced
+    /// as the iterator is driven, so they can be fed into further rayon
+    /// processing without collecting them first. Driving the iterator panics
+    /// if a string can no longer be sliced where the suffix ended, which only
+    /// happens when its `as_ref` returns something different the second time.
+    ///
+    /// Returns `None` when there is no common suffix.
+    fn suffix_stripped_iter(&self) -> Option<impl ParallelIterator<Item = &str>>;
+
+    /// Same as [`CommonStr::common_prefix`], but stops early with [`Cancelled`]
+    /// once `cancel` gets set.
+    ///
+    /// Cancellation is best-effort: `cancel` is checked before every comparison,
+    /// so threads finish the comparison they're in the middle of, and a few more
+    /// elements might get looked at before the query actually stops.
+    fn common_prefix_cancellable(&self, cancel: &AtomicBool) -> Result<Option<String>, Cancelled>;
+
+    /// Same as [`CommonStr::common_suffix`], but stops early with [`Cancelled`]
+    /// once `cancel` gets set.
+    ///
+    /// Cancellation is best-effort: `cancel` is checked before every comparison,
+    /// so threads finish the comparison they're in the middle of, and a few more
+    /// elements might get looked at before the query actually stops.
+    fn common_suffix_cancellable(&self, cancel: &AtomicBool) -> Result<Option<String>, Cancelled>;
+
+    /// Returns the common "skeleton" prefix of all strings, where any two runs of
+    /// ASCII digits are considered equal regardless of their contents or lengths.
+    ///
+    /// The skeleton is the common prefix with every digit run removed, so
+    /// `img001.png`, `img42.png`, and `img7.jpg` give `img.`, while `a12b` and `a345bc`
+    /// give `ab`. A prefix may end right after a digit run, which then isn't visible
+    /// in the skeleton: `v1` and `v23` give `v`. Only ASCII digits are recognized.
+    ///
+    /// Returns `None` when the skeleton is empty, which includes prefixes consisting
+    /// of nothing but digits.
+    fn common_prefix_ignoring_digit_runs(&self) -> Option<String>;
+
+    /// Returns the longest common prefix of all strings, cut back to the last
+    /// whole word, for things like autocomplete suggestions.
+    ///
+    /// Words are separated by ASCII whitespace. When the common prefix ends in the
+    /// middle of a word in any of the strings, it's cut back to just after the last
+    /// whitespace it contains, so `hello wor` and `hello wonder` give `hello ` (with
+    /// the space included). A prefix ending where every string ends or continues
+    /// with whitespace already ends on a whole word, so `hello` and `hello there` give `hello`.
+    ///
+    /// Returns `None` when there is no common prefix, or it doesn't contain a whole word.
+    fn common_prefix_word(&self) -> Option<String>;
+
+    /// Returns whether every string in the collection starts with `prefix`.
+    ///
+    /// Method form of [`verify_common_prefix`], with the same caveats.
+    fn all_start_with(&self, prefix: &str) -> bool;
+
+    /// Returns whether every string in the collection ends with `suffix`.
+    ///
+    /// Method form of [`verify_common_suffix`], with the same caveats.
+    fn all_end_with(&self, suffix: &str) -> bool;
+
+    /// Returns a reference to the longest common prefix of every string,
+    /// ignoring the first `start` bytes of each one.
+    ///
+    /// This is useful for skipping a known header without having to
+    /// slice every string up front.
+    ///
+    /// Returns `None` when there is no common prefix, or when `start` is past
+    /// the end of, or not on a char boundary of, any of the strings.
+    fn common_prefix_from(&self, start: usize) -> Option<&str>;
+
+    /// Partitions the strings by the result of `key`, then finds the
+    /// longest common prefix of every partition.
+    ///
+    /// A partition holding a single string has that string as its prefix. Partitions
+    /// without a common prefix are left out of the returned map entirely, so an empty
+    /// collection (or one where no partition has a common prefix) returns an empty map.
+    fn common_prefix_grouped_by<K, F>(&self, key: F) -> HashMap<K, String>
+    where
+        K: Eq + Hash + Send,
+        F: Fn(&str) -> K + Sync;
+
+    /// Returns the amount of bytes which would be saved by storing the longest
+    /// common prefix once, instead of in every string (its length times one less
+    /// than the amount of strings).
+    ///
+    /// The prefix and the amount of strings are found in a single pass, without
+    /// allocating. Returns 0 when there is no common prefix.
+    fn prefix_savings_bytes(&self) -> usize;
+
+    /// Returns the amount of bytes which would be saved by storing the longest
+    /// common suffix once, instead of in every string (its length times one less
+    /// than the amount of strings).
+    ///
+    /// The suffix and the amount of strings are found in a single pass, without
+    /// allocating. Returns 0 when there is no common suffix.
+    fn suffix_savings_bytes(&self) -> usize;
+
+    /// Returns the length of the longest common prefix divided by the length of the
+    /// shortest string, both in bytes. Both are found in a single pass.
+    ///
+    /// This is a homogeneity score between 0.0 (nothing in common) and 1.0 (the
+    /// shortest string is the common prefix), the latter including collections
+    /// of a single string. A collection holding an empty string scores 0.0.
+    ///
+    /// Returns `None` when the collection is empty.
+    fn common_prefix_ratio(&self) -> Option<f64>;
+
+    /// Returns the length of the longest common suffix divided by the length of the
+    /// shortest string, both in bytes. Both are found in a single pass.
+    ///
+    /// This is a homogeneity score between 0.0 (nothing in common) and 1.0 (the
+    /// shortest string is the common suffix), the latter including collections
+    /// of a single string. A collection holding an empty string scores 0.0.
+    ///
+    /// Returns `None` when the collection is empty.
+    fn common_suffix_ratio(&self) -> Option<f64>;
+
+    /// Same as [`CommonStr::common_prefix_ref`], but rayon won't split the collection
+    /// into pieces smaller than `chunk` strings. Each piece is processed sequentially.
+    ///
+    /// For collections of many short strings this improves locality and avoids
+    /// paying for task management on work which is cheaper than the task itself.
+    /// Only available for collections which can be split at arbitrary indices.
+    fn common_prefix_chunked(&self, chunk: usize) -> Option<&str>
+    where
+        for<'a> &'a Self: IntoParallelIterator<Iter: IndexedParallelIterator>;
+
+    /// Same as [`CommonStr::common_suffix_ref`], but rayon won't split the collection
+    /// into pieces smaller than `chunk` strings. Each piece is processed sequentially.
+    ///
+    /// For collections of many short strings this improves locality and avoids
+    /// paying for task management on work which is cheaper than the task itself.
+    /// Only available for collections which can be split at arbitrary indices.
+    fn common_suffix_chunked(&self, chunk: usize) -> Option<&str>
+    where
+        for<'a> &'a Self: IntoParallelIterator<Iter: IndexedParallelIterator>;
+
+    /// Same as [`CommonStr::common_prefix_ref`], but also returns the index
+    /// of the element the returned reference points into.
+    ///
+    /// Only available for collections which can be split at arbitrary indices.
+    fn common_prefix_ref_indexed(&self) -> Option<(usize, &str)>
+    where
+        for<'a> &'a Self: IntoParallelIterator<Iter: IndexedParallelIterator>;
+
+    /// Same as [`CommonStr::common_suffix_ref`], but also returns the index
+    /// of the element the returned reference points into.
+    ///
+    /// Only available for collections which can be split at arbitrary indices.
+    fn common_suffix_ref_indexed(&self) -> Option<(usize, &str)>
+    where
+        for<'a> &'a Self: IntoParallelIterator<Iter: IndexedParallelIterator>;
+
+    /// Returns the longest common prefix of all strings, along with the indices of
+    /// two strings whose common prefix is exactly that (the pair limiting it).
+    ///
+    /// Useful for finding out why a common prefix is shorter than expected. When several
+    /// pairs qualify, which one gets returned is unspecified. A collection of a single
+    /// string is its own witness, so both indices are the same. Only available for
+    /// collections which can be split at arbitrary indices.
+    ///
+    /// Returns `None` when there is no common prefix.
+    fn prefix_witnesses(&self) -> Option<(String, [usize; 2])>
+    where
+        for<'a> &'a Self: IntoParallelIterator<Iter: IndexedParallelIterator>;
+
+    /// Returns the longest common prefix of all strings, along with a mask where
+    /// element `i` is `true` when the string at index `i` is exactly the prefix,
+    /// with nothing following it.
+    ///
+    /// Useful for telling apart strings which are empty once the shared part gets
+    /// stripped from them. Only available for collections which can be split at
+    /// arbitrary indices.
+    ///
+    /// Returns `None` when there is no common prefix.
+    fn common_prefix_with_exact_mask(&self) -> Option<(String, Vec<bool>)>
+    where
+        for<'a> &'a Self: IntoParallelIterator<Iter: IndexedParallelIterator>;
+
+    /// Returns the longest common prefix of all strings in the collection,
+    /// borrowed from the string at index `idx`.
+    ///
+    /// The prefix is shared by every string, so it can be sliced out of any of them.
+    /// [`CommonStr::common_prefix_ref`] always borrows from the first string, while this
+    /// picks any other one, e.g. to keep a specific allocation alive instead. Only
+    /// available for collections which can be split at arbitrary indices.
+    ///
+    /// Returns `None` when there is no common prefix, or `idx` is out of bounds.
+    fn common_prefix_borrowing_from(&self, idx: usize) -> Option<&str>
+    where
+        for<'a> &'a Self: IntoParallelIterator<Iter: IndexedParallelIterator>;
+
+    /// Returns the longest common suffix of all strings in the collection,
+    /// borrowed from the string at index `idx`.
+    ///
+    /// The suffix is shared by every string, so it can be sliced out of any of them.
+    /// [`CommonStr::common_suffix_ref`] always borrows from the first string, while this
+    /// picks any other one, e.g. to keep a specific allocation alive instead. Only
+    /// available for collections which can be split at arbitrary indices.
+    ///
+    /// Returns `None` when there is no common suffix, or `idx` is out of bounds.
+    fn common_suffix_borrowing_from(&self, idx: usize) -> Option<&str>
+    where
+        for<'a> &'a Self: IntoParallelIterator<Iter: IndexedParallelIterator>;
+
+    /// Estimates the longest common prefix from a sample of about `sample` strings,
+    /// for when a quick preview matters more than an exact answer.
+    ///
+    /// The sample takes every `len / sample`-th string, starting from an offset chosen
+    /// by `seed`, so the same seed always gives the same result. Only the sampled strings
+    /// get looked at, which makes this much faster than [`CommonStr::common_prefix`] on
+    /// huge collections. Only available for collections which can be split at arbitrary
+    /// indices.
+    ///
+    /// **The estimate is never too short:** the actual common prefix is always a prefix
+    /// of it, since any string left out of the sample can only shorten the result.
+    /// When `sample` is at least the length of the collection, every string is
+    /// used, making the result exact.
+    ///
+    /// Returns `None` when there is no common prefix among the sampled strings,
+    /// which includes `sample` being 0.
+    fn common_prefix_sampled(&self, sample: usize, seed: u64) -> Option<String>
+    where
+        for<'a> &'a Self: IntoParallelIterator<Iter: IndexedParallelIterator>;
+
+    /// Returns the indices of all strings which don't start with `expected`,
+    /// in ascending order.
+    ///
+    /// Useful for finding out which elements are responsible for a common prefix
+    /// being shorter than it should be. Only available for collections which can
+    /// be split at arbitrary indices.
+    fn prefix_outliers(&self, expected: &str) -> Vec<usize>
+    where
+        for<'a> &'a Self: IntoParallelIterator<Iter: IndexedParallelIterator>;
+
+    /// Returns the indices of all strings which don't end with `expected`,
+    /// in ascending order.
+    ///
+    /// Useful for finding out which elements are responsible for a common suffix
+    /// being shorter than it should be. Only available for collections which can
+    /// be split at arbitrary indices.
+    fn suffix_outliers(&self, expected: &str) -> Vec<usize>
+    where
+        for<'a> &'a Self: IntoParallelIterator<Iter: IndexedParallelIterator>;
+
+    /// Returns the longest common prefix of every pair of adjacent strings,
+    /// so the element at index `i` belongs to the strings at `i` and `i + 1`.
+    /// A collection of `n` strings yields `n - 1` results.
+    ///
+    /// When the collection is sorted this is the building block of front coding,
+    /// but pairs are compared exactly as given, so sorting isn't required.
+    /// Only available for collections which can be split at arbitrary indices.
+    fn adjacent_common_prefixes(&self) -> Vec<Option<&str>>
+    where
+        for<'a> &'a Self: IntoParallelIterator<Iter: IndexedParallelIterator>;
 }
 
 /// Trait for finding the longest common raw prefix/suffix of any 2D collection.
@@ -96,260 +794,2583 @@ pub trait CommonRaw<T: Clone> {
         self.common_prefix_raw_ref().map(|s| s.to_vec())
     }
 
-    /// Returns the longest common suffix of all referenced data.
-    ///
-    /// Returns `None` when there is no common suffix.
-    fn common_suffix_raw(&self) -> Option<Vec<T>> {
-        self.common_suffix_raw_ref().map(|s| s.to_vec())
-    }
+    /// Returns the longest common suffix of all referenced data.
+    ///
+    /// Returns `None` when there is no common suffix.
+    fn common_suffix_raw(&self) -> Option<Vec<T>> {
+        self.common_suffix_raw_ref().map(|s| s.to_vec())
+    }
+
+    /// Returns the longest common prefix of all referenced data, copied
+    /// into an [`ArrayVec`] instead of a heap allocated [`Vec`].
+    ///
+    /// Returns `Ok(None)` when there is no common prefix, and an error
+    /// when the common prefix is longer than `N`.
+    #[cfg(feature = "arrayvec")]
+    fn common_prefix_raw_inline<const N: usize>(
+        &self,
+    ) -> Result<Option<ArrayVec<T, N>>, CapacityError> {
+        self.common_prefix_raw_ref()
+            .map(ArrayVec::try_from)
+            .transpose()
+    }
+
+    /// Returns the longest common suffix of all referenced data, copied
+    /// into an [`ArrayVec`] instead of a heap allocated [`Vec`].
+    ///
+    /// Returns `Ok(None)` when there is no common suffix, and an error
+    /// when the common suffix is longer than `N`.
+    #[cfg(feature = "arrayvec")]
+    fn common_suffix_raw_inline<const N: usize>(
+        &self,
+    ) -> Result<Option<ArrayVec<T, N>>, CapacityError> {
+        self.common_suffix_raw_ref()
+            .map(ArrayVec::try_from)
+            .transpose()
+    }
+
+    /// Returns the length of the longest common prefix of all referenced data.
+    ///
+    /// Returns `None` instead of 0 when there is no common prefix.
+    fn common_prefix_raw_len(&self) -> Option<NonZeroUsize> {
+        self.common_prefix_raw_ref().map(|s| nonzero_len(s.len()))
+    }
+
+    /// Returns the length of the longest common suffix of all referenced data.
+    ///
+    /// Returns `None` instead of 0 when there is no common suffix.
+    fn common_suffix_raw_len(&self) -> Option<NonZeroUsize> {
+        self.common_suffix_raw_ref().map(|s| nonzero_len(s.len()))
+    }
+
+    /// Same as [`CommonRaw::common_prefix_raw_len`], but returns 0
+    /// when there is no common prefix.
+    fn common_prefix_raw_len_or_zero(&self) -> usize {
+        self.common_prefix_raw_len().map_or(0, NonZeroUsize::get)
+    }
+
+    /// Same as [`CommonRaw::common_suffix_raw_len`], but returns 0
+    /// when there is no common suffix.
+    fn common_suffix_raw_len_or_zero(&self) -> usize {
+        self.common_suffix_raw_len().map_or(0, NonZeroUsize::get)
+    }
+
+    /// Returns an iterator over the elements of the longest common prefix
+    /// of all referenced data, without allocating.
+    ///
+    /// Returns `None` when there is no common prefix.
+    fn common_prefix_raw_iter(&self) -> Option<slice::Iter<'_, T>> {
+        self.common_prefix_raw_ref().map(<[T]>::iter)
+    }
+
+    /// Returns an iterator over the elements of the longest common suffix
+    /// of all referenced data, without allocating.
+    ///
+    /// Returns `None` when there is no common suffix.
+    fn common_suffix_raw_iter(&self) -> Option<slice::Iter<'_, T>> {
+        self.common_suffix_raw_ref().map(<[T]>::iter)
+    }
+
+    /// Returns the longest common prefix of all referenced bytes, formatted
+    /// as space separated hex digits by [`HexSlice`].
+    ///
+    /// Returns `None` when there is no common prefix.
+    fn common_prefix_raw_hex(&self) -> Option<String>
+    where
+        [T]: AsRef<[u8]>,
+    {
+        self.common_prefix_raw_ref()
+            .map(|s| HexSlice(s.as_ref()).to_string())
+    }
+
+    /// Returns the longest common suffix of all referenced bytes, formatted
+    /// as space separated hex digits by [`HexSlice`].
+    ///
+    /// Returns `None` when there is no common suffix.
+    fn common_suffix_raw_hex(&self) -> Option<String>
+    where
+        [T]: AsRef<[u8]>,
+    {
+        self.common_suffix_raw_ref()
+            .map(|s| HexSlice(s.as_ref()).to_string())
+    }
+
+    /// Same as [`CommonRaw::common_prefix_raw`], but executes within `pool`
+    /// instead of rayon's global thread pool.
+    ///
+    /// This is done using [`ThreadPool::install`], so calling it from a thread
+    /// which already belongs to some pool behaves exactly as rayon specifies there.
+    fn common_prefix_raw_in_pool(&self, pool: &ThreadPool) -> Option<Vec<T>>
+    where
+        Self: Sync,
+        T: Send,
+    {
+        pool.install(|| self.common_prefix_raw())
+    }
+
+    /// Same as [`CommonRaw::common_suffix_raw`], but executes within `pool`
+    /// instead of rayon's global thread pool.
+    ///
+    /// This is done using [`ThreadPool::install`], so calling it from a thread
+    /// which already belongs to some pool behaves exactly as rayon specifies there.
+    fn common_suffix_raw_in_pool(&self, pool: &ThreadPool) -> Option<Vec<T>>
+    where
+        Self: Sync,
+        T: Send,
+    {
+        pool.install(|| self.common_suffix_raw())
+    }
+
+    /// Returns a reference to the element which has the longest common
+    /// prefix of all data in the collection.
+    ///
+    /// Returns `None` when there is no common prefix.
+    fn common_prefix_raw_ref(&self) -> Option<&[T]>;
+
+    /// Returns a reference to the element which has the longest common
+    /// suffix of all data in the collection.
+    ///
+    /// Returns `None` when there is no common suffix.
+    fn common_suffix_raw_ref(&self) -> Option<&[T]>;
+
+    /// Same as [`CommonRaw::common_prefix_raw_ref`], but keeps an empty collection
+    /// apart from a collection whose data has nothing in common.
+    ///
+    /// Returns `None` when the collection is empty, and `Some(None)`
+    /// when it isn't but there is no common prefix.
+    fn common_prefix_raw_opt(&self) -> Option<Option<&[T]>>;
+
+    /// Same as [`CommonRaw::common_suffix_raw_ref`], but keeps an empty collection
+    /// apart from a collection whose data has nothing in common.
+    ///
+    /// Returns `None` when the collection is empty, and `Some(None)`
+    /// when it isn't but there is no common suffix.
+    fn common_suffix_raw_opt(&self) -> Option<Option<&[T]>>;
+
+    /// Returns a reference to the longest common prefix of all data,
+    /// ignoring the first `start` elements of each item.
+    ///
+    /// This is useful for skipping a known header without having to
+    /// slice every item up front.
+    ///
+    /// Returns `None` when there is no common prefix, or when any item
+    /// doesn't extend past `start`.
+    fn common_prefix_raw_from(&self, start: usize) -> Option<&[T]>;
+
+    /// Returns a reference to the longest run at the start of every element
+    /// for which `pred` holds, when applied to the elements at the same index.
+    ///
+    /// `pred` isn't required to be an equivalence relation (e.g. "same first letter"),
+    /// but the collection is reduced pairwise in whatever order rayon chooses, so
+    /// a predicate which isn't transitive can produce different results between runs.
+    /// Stick to commutative, transitive predicates if the result needs to be stable.
+    ///
+    /// Returns `None` when there is no such run.
+    fn common_prefix_raw_while<P>(&self, pred: P) -> Option<&[T]>
+    where
+        P: Fn(&T, &T) -> bool + Sync;
+
+    /// Returns a reference to the longest run at the end of every element
+    /// for which `pred` holds, when applied to the elements at the same
+    /// distance from the end.
+    ///
+    /// The same caveats about `pred` as [`CommonRaw::common_prefix_raw_while`] apply.
+    ///
+    /// Returns `None` when there is no such run.
+    fn common_suffix_raw_while<P>(&self, pred: P) -> Option<&[T]>
+    where
+        P: Fn(&T, &T) -> bool + Sync;
+
+    /// Returns a reference to the longest common prefix of every element, with each
+    /// element treated as ending right before its first `sentinel`, like a C string
+    /// ends at its NUL. Elements without a `sentinel` are used in their entirety.
+    ///
+    /// This avoids having to slice every element at its sentinel beforehand.
+    /// The result never contains a `sentinel`.
+    ///
+    /// Returns `None` when there is no common prefix.
+    fn common_prefix_raw_until(&self, sentinel: T) -> Option<&[T]>;
+
+    /// Returns a reference to the longest common prefix of all referenced data,
+    /// always borrowed from the shortest element.
+    ///
+    /// Other methods borrow their result from the first element. This one borrows from
+    /// the shortest element instead (the first one, in iteration order, when several are
+    /// equally short), which is the smallest allocation the result can keep borrowed.
+    /// Finding that element takes an extra pass over the lengths of all elements.
+    ///
+    /// Returns `None` when there is no common prefix.
+    fn common_prefix_raw_from_shortest(&self) -> Option<&[T]>;
+}
+
+impl<C: ?Sized, T> CommonStr for C
+where
+    for<'a> &'a C: IntoParallelIterator<Item = &'a T>,
+    T: AsRef<str> + Sync,
+{
+    fn common_prefix_ref(&self) -> Option<&str> {
+        find_common::<_, StringPrefix, _, _>(self)
+    }
+
+    fn common_suffix_ref(&self) -> Option<&str> {
+        find_common::<_, StringSuffix, _, _>(self)
+    }
+
+    unsafe fn common_prefix_ascii(&self) -> Option<String> {
+        find_common::<_, AsciiPrefix, _, _>(self).map(|s| s.to_string())
+    }
+
+    fn common_prefix_with_cost(&self) -> (Option<&str>, u64) {
+        let cost = AtomicU64::new(0);
+        let iter = self.into_par_iter().map(|s| s.as_ref());
+        let prefix = reduce_by(iter, |a, b| {
+            let (common, compared) = prefix_with_cost(a, b);
+            cost.fetch_add(compared, AtomicOrdering::Relaxed);
+            common
+        });
+        (prefix, cost.into_inner())
+    }
+
+    fn common_prefix_bytes_unchecked(&self) -> Option<&[u8]> {
+        let iter = self.into_par_iter().map(|s| s.as_ref().as_bytes());
+        reduce_common::<BytePrefix, _, _>(iter)
+    }
+
+    fn common_suffix_bytes_unchecked(&self) -> Option<&[u8]> {
+        let iter = self.into_par_iter().map(|s| s.as_ref().as_bytes());
+        reduce_common::<ByteSuffix, _, _>(iter)
+    }
+
+    fn prefix_divergence(&self) -> Vec<usize> {
+        let Some(first) = self.into_par_iter().find_first(|_| true) else {
+            return Vec::new();
+        };
+        let first = first.as_ref();
+        self.into_par_iter()
+            .map(|s| StringPrefix::common(first, s.as_ref()).map_or(0, str::len))
+            .collect()
+    }
+
+    fn common_prefix_sorted(&self) -> Option<&str> {
+        let first = self
+            .into_par_iter()
+            .map(|s| s.as_ref())
+            .find_first(|_| true)?;
+        let last = self
+            .into_par_iter()
+            .map(|s| s.as_ref())
+            .find_last(|_| true)?;
+        // Every string lying between the two endpoints is all we actually rely on.
+        debug_assert!(
+            self.into_par_iter()
+                .all(|s| (first..=last).contains(&s.as_ref())),
+            "collection passed to `common_prefix_sorted` isn't sorted"
+        );
+        StringPrefix::common(first, last)
+    }
+
+    fn common_prefix_shortest_first(&self) -> Option<&str> {
+        let shortest = self
+            .into_par_iter()
+            .map(|s| s.as_ref())
+            .min_by_key(|s| s.len())?;
+        match self
+            .into_par_iter()
+            .all(|s| s.as_ref().starts_with(shortest))
+        {
+            true => (!shortest.is_empty()).then_some(shortest),
+            false => self.common_prefix_ref(),
+        }
+    }
+
+    fn common_prefix_dedup(&self) -> Option<String> {
+        let unique: HashSet<&str> = self.into_par_iter().map(|s| s.as_ref()).collect();
+        reduce_common::<StringPrefix, _, _>(unique.into_par_iter()).map(str::to_string)
+    }
+
+    fn common_prefix_of_longest(&self, k: usize) -> Option<&str> {
+        if k == 0 {
+            return None;
+        }
+        let mut strings: Vec<&str> = self.into_par_iter().map(|s| s.as_ref()).collect();
+        if k < strings.len() {
+            strings.select_nth_unstable_by_key(k - 1, |s| Reverse(s.len()));
+            strings.truncate(k);
+        }
+        reduce_common::<StringPrefix, _, _>(strings.into_par_iter())
+    }
+
+    fn prefix_coverage_tiers(&self, thresholds: &[f64]) -> Vec<(f64, Option<String>)> {
+        let mut sorted: Vec<&str> = self.into_par_iter().map(|s| s.as_ref()).collect();
+        sorted.par_sort_unstable();
+        let tier = |threshold: f64| {
+            // Products like `0.07 * 100.0` come out a hair above the whole number they
+            // stand for, which mustn't be rounded up to require one more string.
+            let scaled = threshold * sorted.len() as f64;
+            let needed = ((scaled - scaled.abs() * 1e-12).ceil() as usize).max(1);
+            // Earlier runs hold lexicographically smaller strings, and
+            // `reduce_with` keeps the first of the longest prefixes.
+            sorted
+                .par_windows(needed)
+                .filter_map(|run| StringPrefix::common(run[0], run[needed - 1]))
+                .reduce_with(|a, b| if b.len() > a.len() { b } else { a })
+                .map(|s| s.to_string())
+        };
+        thresholds.iter().map(|&t| (t, tier(t))).collect()
+    }
+
+    fn prefix_stripped_iter(&self) -> Option<impl ParallelIterator<Item = &str>> {
+        let len = self.common_prefix_ref()?.len();
+        // Strings are borrowed again when the iterator gets driven, and nothing stops
+        // `as_ref` from returning a different string by then, so this has to be checked.
+        Some(self.into_par_iter().map(move |s| {
+            s.as_ref()
+                .get(len..)
+                .expect("a string changed after its common prefix was found")
+        }))
+    }
+
+    fn suffix_stripped_iter(&self) -> Option<impl ParallelIterator<Item = &str>> {
+        let len = self.common_suffix_ref()?.len();
+        Some(self.into_par_iter().map(move |s| {
+            let s = s.as_ref();
+            s.len()
+                .checked_sub(len)
+                .and_then(|end| s.get(..end))
+                .expect("a string changed after its common suffix was found")
+        }))
+    }
+
+    fn lexicographic_bounds(&self) -> Option<(&str, &str)> {
+        self.into_par_iter()
+            .map(|s| (s.as_ref(), s.as_ref()))
+            .reduce_with(|(a_min, a_max), (b_min, b_max)| (a_min.min(b_min), a_max.max(b_max)))
+    }
+
+    fn common_prefix_cancellable(&self, cancel: &AtomicBool) -> Result<Option<String>, Cancelled> {
+        let iter = self.into_par_iter().map(|s| s.as_ref());
+        cancellable::<StringPrefix, _>(iter, cancel)
+    }
+
+    fn common_suffix_cancellable(&self, cancel: &AtomicBool) -> Result<Option<String>, Cancelled> {
+        let iter = self.into_par_iter().map(|s| s.as_ref());
+        cancellable::<StringSuffix, _>(iter, cancel)
+    }
+
+    fn common_prefix_ignoring_digit_runs(&self) -> Option<String> {
+        let template = find_common::<_, DigitRunPrefix, _, _>(self)?;
+        let skeleton: String = template.chars().filter(|c| !c.is_ascii_digit()).collect();
+        match skeleton.is_empty() {
+            true => None,
+            false => Some(skeleton),
+        }
+    }
+
+    fn common_prefix_word(&self) -> Option<String> {
+        let prefix = self.common_prefix_ref()?;
+        let len = prefix.len();
+        let whole = self.into_par_iter().all(|s| {
+            let next = s.as_ref().as_bytes().get(len);
+            next.is_none_or(u8::is_ascii_whitespace)
+        });
+        let end = match whole {
+            true => len,
+            false => prefix.rfind(|c: char| c.is_ascii_whitespace())? + 1,
+        };
+        Some(get_unchecked!(prefix, ..end).to_string())
+    }
+
+    fn all_start_with(&self, prefix: &str) -> bool {
+        verify_common_prefix(self, prefix)
+    }
+
+    fn all_end_with(&self, suffix: &str) -> bool {
+        verify_common_suffix(self, suffix)
+    }
+
+    fn common_prefix_from(&self, start: usize) -> Option<&str> {
+        // Strings which can't be sliced at `start` become empty, which
+        // nothing has a common prefix with.
+        let iter = self
+            .into_par_iter()
+            .map(|s| s.as_ref().get(start..).unwrap_or_default());
+        reduce_common::<StringPrefix, _, _>(iter)
+    }
+
+    fn common_prefix_grouped_by<K, F>(&self, key: F) -> HashMap<K, String>
+    where
+        K: Eq + Hash + Send,
+        F: Fn(&str) -> K + Sync,
+    {
+        self.into_par_iter()
+            .map(|s| s.as_ref())
+            .fold(HashMap::new, |map, s| merge_group(map, (key(s), Some(s))))
+            .reduce(HashMap::new, |a, b| b.into_iter().fold(a, merge_group))
+            .into_iter()
+            .filter_map(|(k, s)| Some((k, s?.to_string())))
+            .collect()
+    }
+
+    fn prefix_savings_bytes(&self) -> usize {
+        let iter = self.into_par_iter().map(|s| (1, s.as_ref()));
+        reduce_by(iter, |(n, a), (m, b)| {
+            Some((n + m, StringPrefix::common(a, b)?))
+        })
+        .map_or(0, |(count, prefix)| prefix.len() * (count - 1))
+    }
+
+    fn suffix_savings_bytes(&self) -> usize {
+        let iter = self.into_par_iter().map(|s| (1, s.as_ref()));
+        reduce_by(iter, |(n, a), (m, b)| {
+            Some((n + m, StringSuffix::common(a, b)?))
+        })
+        .map_or(0, |(count, suffix)| suffix.len() * (count - 1))
+    }
+
+    fn common_prefix_ratio(&self) -> Option<f64> {
+        let iter = self.into_par_iter().map(|s| (s.as_ref().len(), s.as_ref()));
+        match reduce_by(iter, |(n, a), (m, b)| {
+            Some((n.min(m), StringPrefix::common(a, b)?))
+        }) {
+            Some((shortest, prefix)) => Some(len_ratio(prefix.len(), shortest)),
+            None => self.into_par_iter().any(|_| true).then_some(0.0),
+        }
+    }
+
+    fn common_suffix_ratio(&self) -> Option<f64> {
+        let iter = self.into_par_iter().map(|s| (s.as_ref().len(), s.as_ref()));
+        match reduce_by(iter, |(n, a), (m, b)| {
+            Some((n.min(m), StringSuffix::common(a, b)?))
+        }) {
+            Some((shortest, suffix)) => Some(len_ratio(suffix.len(), shortest)),
+            None => self.into_par_iter().any(|_| true).then_some(0.0),
+        }
+    }
+
+    fn common_prefix_chunked(&self, chunk: usize) -> Option<&str>
+    where
+        for<'a> &'a Self: IntoParallelIterator<Iter: IndexedParallelIterator>,
+    {
+        let iter = self.into_par_iter().map(|s| s.as_ref());
+        reduce_common::<StringPrefix, _, _>(iter.with_min_len(chunk))
+    }
+
+    fn common_suffix_chunked(&self, chunk: usize) -> Option<&str>
+    where
+        for<'a> &'a Self: IntoParallelIterator<Iter: IndexedParallelIterator>,
+    {
+        let iter = self.into_par_iter().map(|s| s.as_ref());
+        reduce_common::<StringSuffix, _, _>(iter.with_min_len(chunk))
+    }
+
+    fn common_prefix_ref_indexed(&self) -> Option<(usize, &str)>
+    where
+        for<'a> &'a Self: IntoParallelIterator<Iter: IndexedParallelIterator>,
+    {
+        let iter = self.into_par_iter().map(|s| s.as_ref()).enumerate();
+        reduce_by(iter, |(i, a), (_, b)| {
+            Some((i, StringPrefix::common(a, b)?))
+        })
+    }
+
+    fn common_suffix_ref_indexed(&self) -> Option<(usize, &str)>
+    where
+        for<'a> &'a Self: IntoParallelIterator<Iter: IndexedParallelIterator>,
+    {
+        let iter = self.into_par_iter().map(|s| s.as_ref()).enumerate();
+        reduce_by(iter, |(i, a), (_, b)| {
+            Some((i, StringSuffix::common(a, b)?))
+        })
+    }
+
+    fn prefix_witnesses(&self) -> Option<(String, [usize; 2])>
+    where
+        for<'a> &'a Self: IntoParallelIterator<Iter: IndexedParallelIterator>,
+    {
+        let iter = self
+            .into_par_iter()
+            .enumerate()
+            .map(|(i, s)| (s.as_ref(), [i; 2]));
+        let (prefix, witnesses) = reduce_by(iter, |(a, a_pair), (b, b_pair)| {
+            let common = StringPrefix::common(a, b)?;
+            // Whichever side didn't shrink is still limited by its own pair. When both
+            // shrank, any string from either side diverges from any string of the other
+            // right where `a` and `b` do.
+            let pair = match common.len() {
+                len if len == a.len() => a_pair,
+                len if len == b.len() => b_pair,
+                _ => [a_pair[0], b_pair[0]],
+            };
+            Some((common, pair))
+        })?;
+        Some((prefix.to_string(), witnesses))
+    }
+
+    fn common_prefix_with_exact_mask(&self) -> Option<(String, Vec<bool>)>
+    where
+        for<'a> &'a Self: IntoParallelIterator<Iter: IndexedParallelIterator>,
+    {
+        let prefix = self.common_prefix_ref()?;
+        // Every string starts with the prefix, so only their lengths need comparing.
+        let mask = self
+            .into_par_iter()
+            .map(|s| s.as_ref().len() == prefix.len())
+            .collect();
+        Some((prefix.to_string(), mask))
+    }
+
+    fn common_prefix_borrowing_from(&self, idx: usize) -> Option<&str>
+    where
+        for<'a> &'a Self: IntoParallelIterator<Iter: IndexedParallelIterator>,
+    {
+        let element = self
+            .into_par_iter()
+            .map(|s| s.as_ref())
+            .skip(idx)
+            .find_first(|_| true)?;
+        // Comparing against `element` itself rather than slicing it by the length of
+        // the prefix, since `as_ref` may have returned a different string this time.
+        StringPrefix::common(element, self.common_prefix_ref()?)
+    }
+
+    fn common_suffix_borrowing_from(&self, idx: usize) -> Option<&str>
+    where
+        for<'a> &'a Self: IntoParallelIterator<Iter: IndexedParallelIterator>,
+    {
+        let element = self
+            .into_par_iter()
+            .map(|s| s.as_ref())
+            .skip(idx)
+            .find_first(|_| true)?;
+        StringSuffix::common(element, self.common_suffix_ref()?)
+    }
+
+    fn common_prefix_sampled(&self, sample: usize, seed: u64) -> Option<String>
+    where
+        for<'a> &'a Self: IntoParallelIterator<Iter: IndexedParallelIterator>,
+    {
+        let iter = self.into_par_iter().map(|s| s.as_ref());
+        let stride = iter.len().checked_div(sample)?.max(1);
+        let offset = (mix(seed) % stride as u64) as usize;
+        let iter = iter.skip(offset).step_by(stride);
+        reduce_common::<StringPrefix, _, _>(iter).map(|s| s.to_string())
+    }
+
+    fn prefix_outliers(&self, expected: &str) -> Vec<usize>
+    where
+        for<'a> &'a Self: IntoParallelIterator<Iter: IndexedParallelIterator>,
+    {
+        self.into_par_iter()
+            .enumerate()
+            .filter(|(_, s)| !s.as_ref().starts_with(expected))
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    fn suffix_outliers(&self, expected: &str) -> Vec<usize>
+    where
+        for<'a> &'a Self: IntoParallelIterator<Iter: IndexedParallelIterator>,
+    {
+        self.into_par_iter()
+            .enumerate()
+            .filter(|(_, s)| !s.as_ref().ends_with(expected))
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    fn adjacent_common_prefixes(&self) -> Vec<Option<&str>>
+    where
+        for<'a> &'a Self: IntoParallelIterator<Iter: IndexedParallelIterator>,
+    {
+        let current = self.into_par_iter();
+        let next = self.into_par_iter().skip(1);
+        current
+            .zip(next)
+            .map(|(a, b)| StringPrefix::common(a.as_ref(), b.as_ref()))
+            .collect()
+    }
+}
+
+impl<C: ?Sized, T, U> CommonRaw<U> for C
+where
+    for<'a> &'a C: IntoParallelIterator<Item = &'a T>,
+    T: AsRef<[U]> + Sync,
+    U: Clone + Eq + Sync,
+{
+    fn common_prefix_raw_ref(&self) -> Option<&[U]> {
+        find_common::<_, GenericPrefix, _, _>(self)
+    }
+
+    fn common_suffix_raw_ref(&self) -> Option<&[U]> {
+        find_common::<_, GenericSuffix, _, _>(self)
+    }
+
+    fn common_prefix_raw_opt(&self) -> Option<Option<&[U]>> {
+        match self.common_prefix_raw_ref() {
+            Some(prefix) => Some(Some(prefix)),
+            // Only paying for this check when there's nothing in common.
+            None => self.into_par_iter().any(|_| true).then_some(None),
+        }
+    }
+
+    fn common_suffix_raw_opt(&self) -> Option<Option<&[U]>> {
+        match self.common_suffix_raw_ref() {
+            Some(suffix) => Some(Some(suffix)),
+            // Only paying for this check when there's nothing in common.
+            None => self.into_par_iter().any(|_| true).then_some(None),
+        }
+    }
+
+    fn common_prefix_raw_from(&self, start: usize) -> Option<&[U]> {
+        // Items which end before `start` become empty, which
+        // nothing has a common prefix with.
+        let iter = self
+            .into_par_iter()
+            .map(|s| s.as_ref().get(start..).unwrap_or_default());
+        reduce_common::<GenericPrefix, _, _>(iter)
+    }
+
+    fn common_prefix_raw_while<P>(&self, pred: P) -> Option<&[U]>
+    where
+        P: Fn(&U, &U) -> bool + Sync,
+    {
+        let iter = self.into_par_iter().map(|s| s.as_ref());
+        reduce_by(iter, |a, b| prefix_while(a, b, &pred))
+    }
+
+    fn common_suffix_raw_while<P>(&self, pred: P) -> Option<&[U]>
+    where
+        P: Fn(&U, &U) -> bool + Sync,
+    {
+        let iter = self.into_par_iter().map(|s| s.as_ref());
+        reduce_by(iter, |a, b| suffix_while(a, b, &pred))
+    }
+
+    fn common_prefix_raw_from_shortest(&self) -> Option<&[U]> {
+        let shortest = self
+            .into_par_iter()
+            .map(|s| s.as_ref())
+            .min_by_key(|s| s.len())?;
+        // Comparing against `shortest` itself rather than slicing it by the length of
+        // the prefix, since `as_ref` may have returned a different slice this time.
+        GenericPrefix::common(shortest, self.common_prefix_raw_ref()?)
+    }
+
+    fn common_prefix_raw_until(&self, sentinel: U) -> Option<&[U]> {
+        let iter = self.into_par_iter().map(|s| {
+            let s = s.as_ref();
+            match s.iter().position(|x| *x == sentinel) {
+                Some(end) => get_unchecked!(s, ..end),
+                None => s,
+            }
+        });
+        reduce_common::<GenericPrefix, _, _>(iter)
+    }
+}
+
+/// Returns the longest common prefix of all strings referenced by
+/// both `a` and `b`, as if they were a single collection.
+///
+/// This avoids having to merge the two collections beforehand, and
+/// they aren't required to hold the same type of string.
+///
+/// Returns `None` when there is no common prefix.
+pub fn common_prefix_of<'a, A, B, T, U>(a: &'a A, b: &'a B) -> Option<String>
+where
+    A: ?Sized,
+    B: ?Sized,
+    &'a A: IntoParallelIterator<Item = &'a T>,
+    &'a B: IntoParallelIterator<Item = &'a U>,
+    T: AsRef<str> + Sync + 'a,
+    U: AsRef<str> + Sync + 'a,
+{
+    let a = a.into_par_iter().map(|s| s.as_ref());
+    let b = b.into_par_iter().map(|s| s.as_ref());
+    reduce_common::<StringPrefix, _, _>(a.chain(b)).map(|s| s.to_string())
+}
+
+/// Returns the longest common suffix of all strings referenced by
+/// both `a` and `b`, as if they were a single collection.
+///
+/// This avoids having to merge the two collections beforehand, and
+/// they aren't required to hold the same type of string.
+///
+/// Returns `None` when there is no common suffix.
+pub fn common_suffix_of<'a, A, B, T, U>(a: &'a A, b: &'a B) -> Option<String>
+where
+    A: ?Sized,
+    B: ?Sized,
+    &'a A: IntoParallelIterator<Item = &'a T>,
+    &'a B: IntoParallelIterator<Item = &'a U>,
+    T: AsRef<str> + Sync + 'a,
+    U: AsRef<str> + Sync + 'a,
+{
+    let a = a.into_par_iter().map(|s| s.as_ref());
+    let b = b.into_par_iter().map(|s| s.as_ref());
+    reduce_common::<StringSuffix, _, _>(a.chain(b)).map(|s| s.to_string())
+}
+
+/// Returns the longest common prefix of all lines of `text`, as if they
+/// were the strings of a collection.
+///
+/// Lines are split the same way as [`str::lines`]: they end with either `\n` or `\r\n`,
+/// neither of which is part of the line, and a trailing line ending doesn't start
+/// another (empty) line. This makes it easy to find the indentation or log prefix
+/// shared by a multi-line blob without collecting its lines first.
+///
+/// Returns `None` when there is no common prefix, which is always the case
+/// when `text` contains an empty line.
+pub fn common_line_prefix(text: &str) -> Option<String> {
+    reduce_common::<StringPrefix, _, _>(text.par_lines()).map(|s| s.to_string())
+}
+
+/// Returns the longest common suffix of all lines of `text`, as if they
+/// were the strings of a collection.
+///
+/// Lines are split the same way as [`common_line_prefix`] splits them, so
+/// line endings are never part of the suffix.
+///
+/// Returns `None` when there is no common suffix, which is always the case
+/// when `text` contains an empty line.
+pub fn common_line_suffix(text: &str) -> Option<String> {
+    reduce_common::<StringSuffix, _, _>(text.par_lines()).map(|s| s.to_string())
+}
+
+/// Returns both the longest common prefix and the longest common
+/// suffix of `a` and `b`, borrowed from `a`.
+///
+/// Short strings are compared from both ends in a single pass, which is
+/// cheaper than finding the prefix and suffix separately.
+pub fn common_prefix_and_suffix_pair<'a>(
+    a: &'a str,
+    b: &str,
+) -> (Option<&'a str>, Option<&'a str>) {
+    prefix_and_suffix(a, b)
+}
+
+/// Returns the longest common prefix of `first` and `last`, borrowed from `first`.
+///
+/// This is the two string comparison behind [`CommonStr::common_prefix_sorted`]:
+/// **every string of the collection must lie lexicographically between `first` and
+/// `last`**, as the first and last strings of a sorted collection do. Any string
+/// that does shares their common prefix, so this is the common prefix of all of them.
+/// [`CommonStr::lexicographic_bounds`] finds such endpoints for unsorted collections.
+///
+/// The result is unspecified when the precondition doesn't hold. Two strings on their own
+/// always satisfy it, which makes this the cheapest way to find their common prefix.
+///
+/// Returns `None` when there is no common prefix.
+pub fn common_prefix_endpoints<'a>(first: &'a str, last: &str) -> Option<&'a str> {
+    StringPrefix::common(first, last)
+}
+
+/// Returns the longest common prefix of `a` and `b`, borrowed from `a`, along
+/// with the bytes of `a` and `b` (in that order) at the first position where they differ.
+///
+/// The bytes are taken from where the strings first differ, even when that's in the
+/// middle of a char, and the prefix was moved back onto a char boundary. This makes
+/// them usable for deciding which branch of a byte-level radix tree a string takes.
+/// When one string is a prefix of the other (or they're equal), nothing differs
+/// before one of them ends, so the second value is `None`.
+pub fn common_prefix_with_diff<'a>(a: &'a str, b: &str) -> (Option<&'a str>, Option<(u8, u8)>) {
+    prefix_with_diff(a, b)
+}
+
+/// Returns the longest common prefix of the `len` strings returned by `get`
+/// for every index from 0 up to `len`.
+///
+/// This allows reading strings straight out of layouts which aren't a collection of
+/// them, like a field of every record in struct-of-arrays data, without gathering them
+/// first. Indices are split between rayon's threads, so `get` must be [`Sync`].
+///
+/// Returns `None` when there is no common prefix.
+pub fn common_prefix_from_fn<'a, F>(len: usize, get: F) -> Option<&'a str>
+where
+    F: Fn(usize) -> &'a str + Sync,
+{
+    reduce_common::<StringPrefix, _, _>((0..len).into_par_iter().map(&get))
+}
+
+/// Returns the longest common suffix of the `len` strings returned by `get`
+/// for every index from 0 up to `len`.
+///
+/// This allows reading strings straight out of layouts which aren't a collection of
+/// them, like a field of every record in struct-of-arrays data, without gathering them
+/// first. Indices are split between rayon's threads, so `get` must be [`Sync`].
+///
+/// Returns `None` when there is no common suffix.
+pub fn common_suffix_from_fn<'a, F>(len: usize, get: F) -> Option<&'a str>
+where
+    F: Fn(usize) -> &'a str + Sync,
+{
+    reduce_common::<StringSuffix, _, _>((0..len).into_par_iter().map(&get))
+}
+
+/// Returns whether every string in `collection` starts with `prefix`, checked in parallel.
+///
+/// Meant as a post-condition for results obtained through any of the optimized paths.
+/// This only checks that `prefix` is common, not that it's the longest one. The length
+/// to compare is known upfront, so each string is checked with a single `memcmp`, which
+/// beats the chunked comparisons used for finding common prefixes. Every string of an
+/// empty collection vacuously starts with anything, so that returns `true`.
+pub fn verify_common_prefix<C: ?Sized, T>(collection: &C, prefix: &str) -> bool
+where
+    for<'a> &'a C: IntoParallelIterator<Item = &'a T>,
+    T: AsRef<str> + Sync,
+{
+    collection
+        .into_par_iter()
+        .all(|s| s.as_ref().starts_with(prefix))
+}
+
+/// Returns whether every string in `collection` ends with `suffix`, checked in parallel.
+///
+/// Meant as a post-condition for results obtained through any of the optimized paths.
+/// This only checks that `suffix` is common, not that it's the longest one. The length
+/// to compare is known upfront, so each string is checked with a single `memcmp`, which
+/// beats the chunked comparisons used for finding common suffixes. Every string of an
+/// empty collection vacuously ends with anything, so that returns `true`.
+pub fn verify_common_suffix<C: ?Sized, T>(collection: &C, suffix: &str) -> bool
+where
+    for<'a> &'a C: IntoParallelIterator<Item = &'a T>,
+    T: AsRef<str> + Sync,
+{
+    collection
+        .into_par_iter()
+        .all(|s| s.as_ref().ends_with(suffix))
+}
+
+/// Returns whether every slice in `collection` starts with `prefix`, checked in parallel.
+///
+/// Raw counterpart to [`verify_common_prefix`], with the same caveats.
+pub fn verify_common_prefix_raw<C: ?Sized, T, U>(collection: &C, prefix: &[U]) -> bool
+where
+    for<'a> &'a C: IntoParallelIterator<Item = &'a T>,
+    T: AsRef<[U]> + Sync,
+    U: Eq + Sync,
+{
+    collection
+        .into_par_iter()
+        .all(|s| s.as_ref().starts_with(prefix))
+}
+
+/// Returns whether every slice in `collection` ends with `suffix`, checked in parallel.
+///
+/// Raw counterpart to [`verify_common_suffix`], with the same caveats.
+pub fn verify_common_suffix_raw<C: ?Sized, T, U>(collection: &C, suffix: &[U]) -> bool
+where
+    for<'a> &'a C: IntoParallelIterator<Item = &'a T>,
+    T: AsRef<[U]> + Sync,
+    U: Eq + Sync,
+{
+    collection
+        .into_par_iter()
+        .all(|s| s.as_ref().ends_with(suffix))
+}
+
+/// Compares how homogeneous the strings of `a` and `b` are, returning
+/// [`Ordering::Greater`] when `a` is more homogeneous than `b`.
+///
+/// The collection with the longer common prefix is more homogeneous. When both
+/// prefixes are equally long (including when neither has one), the collection
+/// with more strings is, since it takes more to keep sharing the same prefix.
+pub fn compare_homogeneity<'a, A, B, T, U>(a: &'a A, b: &'a B) -> Ordering
+where
+    A: ?Sized,
+    B: ?Sized,
+    &'a A: IntoParallelIterator<Item = &'a T>,
+    &'a B: IntoParallelIterator<Item = &'a U>,
+    T: AsRef<str> + Sync + 'a,
+    U: AsRef<str> + Sync + 'a,
+{
+    let a_len = reduce_common::<StringPrefix, _, _>(a.into_par_iter().map(|s| s.as_ref()));
+    let b_len = reduce_common::<StringPrefix, _, _>(b.into_par_iter().map(|s| s.as_ref()));
+    let a_len = a_len.map_or(0, str::len);
+    let b_len = b_len.map_or(0, str::len);
+    a_len
+        .cmp(&b_len)
+        .then_with(|| a.into_par_iter().count().cmp(&b.into_par_iter().count()))
+}
+
+/// Returns the longest common prefix of all strings yielded by `iter`.
+///
+/// `iter` only needs to be a regular [`Iterator`], which gets parallelized using
+/// [`ParallelBridge::par_bridge`], so lazily generated strings don't have to be
+/// collected first. Bridging has a fixed overhead per item, so this is only worth
+/// it over collecting when the items are expensive to produce.
+///
+/// Returns `None` when there is no common prefix.
+pub fn common_prefix_bridge<I, S>(iter: I) -> Option<String>
+where
+    I: Iterator<Item = S> + Send,
+    S: AsRef<str> + Send,
+{
+    bridge_common::<StringPrefix, _, _>(iter)
+}
+
+/// Returns the longest common suffix of all strings yielded by `iter`.
+///
+/// `iter` only needs to be a regular [`Iterator`], which gets parallelized using
+/// [`ParallelBridge::par_bridge`], so lazily generated strings don't have to be
+/// collected first. Bridging has a fixed overhead per item, so this is only worth
+/// it over collecting when the items are expensive to produce.
+///
+/// Returns `None` when there is no common suffix.
+pub fn common_suffix_bridge<I, S>(iter: I) -> Option<String>
+where
+    I: Iterator<Item = S> + Send,
+    S: AsRef<str> + Send,
+{
+    bridge_common::<StringSuffix, _, _>(iter)
+}
+
+/// Counterpart to [`reduce_by`] for items which can't be borrowed from
+/// a collection. Each partial result is an owned [`String`] which gets
+/// shrunk in place, so only the first item of every fold allocates.
+fn bridge_common<F, I, S>(iter: I) -> Option<String>
+where
+    F: Finder<str>,
+    I: Iterator<Item = S> + Send,
+    S: AsRef<str> + Send,
+{
+    iter.par_bridge()
+        .try_fold(
+            || None,
+            |previous: Option<String>, current| match previous {
+                Some(prev) => keep_common::<F>(prev, current.as_ref()).map(Some),
+                None => Some(Some(current.as_ref().to_string())),
+            },
+        )
+        .try_reduce(
+            || None,
+            |a, b| match (a, b) {
+                (Some(a), Some(b)) => keep_common::<F>(a, &b).map(Some),
+                (Some(common), None) | (None, Some(common)) => Some(Some(common)),
+                (None, None) => Some(None),
+            },
+        )
+        .flatten()
+        .filter(|common| !common.is_empty())
+}
+
+/// Shrinks `s` down to the part of it which [`Finder::common`] returns.
+fn keep_common<F: Finder<str>>(mut s: String, other: &str) -> Option<String> {
+    let common = F::common(&s, other)?;
+    let begin = common.as_ptr().addr() - s.as_ptr().addr();
+    let end = begin + common.len();
+    s.truncate(end);
+    s.drain(..begin);
+    Some(s)
+}
+
+/// Scrambles `seed` (using the SplitMix64 finalizer), so that similar
+/// seeds still pick unrelated samples.
+fn mix(seed: u64) -> u64 {
+    let mut z = seed.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+/// Returns `len / shortest`, where an empty shortest string means nothing is shared.
+fn len_ratio(len: usize, shortest: usize) -> f64 {
+    match shortest {
+        0 => 0.0,
+        _ => len as f64 / shortest as f64,
+    }
+}
+
+/// Merges the partial prefix `s` of group `k` into `map`, for use by
+/// [`CommonStr::common_prefix_grouped_by`]. A group mapping to `None` has been
+/// found to have no common prefix, which needs to be remembered so later
+/// strings don't revive it.
+fn merge_group<'a, K: Eq + Hash>(
+    mut map: HashMap<K, Option<&'a str>>,
+    (k, s): (K, Option<&'a str>),
+) -> HashMap<K, Option<&'a str>> {
+    map.entry(k)
+        .and_modify(|prev| *prev = prev.zip(s).and_then(|(a, b)| StringPrefix::common(a, b)))
+        .or_insert(s);
+    map
+}
+
+/// Same as [`reduce_common`], but bails out as soon as `cancel` is set.
+/// Bailing looks just like finding nothing in common to the reduction,
+/// so whether it happened needs to be tracked separately.
+fn cancellable<'a, F, I>(iter: I, cancel: &AtomicBool) -> Result<Option<String>, Cancelled>
+where
+    F: Finder<str>,
+    I: ParallelIterator<Item = &'a str>,
+{
+    let bailed = AtomicBool::new(false);
+    let result = reduce_by(iter, |a, b| {
+        if cancel.load(AtomicOrdering::Relaxed) {
+            bailed.store(true, AtomicOrdering::Relaxed);
+            return None;
+        }
+        F::common(a, b)
+    });
+    match bailed.into_inner() {
+        true => Err(Cancelled),
+        false => Ok(result.map(|s| s.to_string())),
+    }
+}
+
+/// Convenience wrapper around [`reduce_common`] for the common case
+/// where every item of the collection can be directly referenced as `U`.
+fn find_common<C: ?Sized, F, T, U>(collection: &C) -> Option<&U>
+where
+    for<'a> &'a C: IntoParallelIterator<Item = &'a T>,
+    F: Finder<U>,
+    T: AsRef<U> + Sync,
+    U: ?Sized + Sync,
+    for<'a> &'a U: Reducible,
+{
+    reduce_common::<F, _, _>(collection.into_par_iter().map(|t| t.as_ref()))
+}
+
+/// Core function for finding LCP or LCS, where each pair of
+/// referenced values is combined using [`Finder::common`].
+fn reduce_common<'a, F, U, I>(iter: I) -> Option<&'a U>
+where
+    I: ParallelIterator<Item = &'a U>,
+    F: Finder<U>,
+    U: ?Sized + Sync + 'a,
+    &'a U: Reducible,
+{
+    reduce_by(iter, F::common)
+}
+
+/// Does the actual work behind [`reduce_common`]. It looks a bit involved,
+/// but most of what goes on in here is just to ensure we satisfy the
+/// type constraints laid out by rayon.
+///
+/// The core idea is to, for each pair of values, compute the result of
+/// `common` and pass it along to be one of the values in the next pair.
+/// At any point, that result might be `None` (there was no common prefix/suffix),
+/// causing the routine to terminate as soon as rayon is able to halt execution.
+///
+/// A collection of a single value never forms a pair, so that value is the result.
+/// `common` never returns empty values, so an empty one gets turned into `None` here,
+/// making results equally meaningful no matter how many values there were.
+fn reduce_by<I, T, F>(iter: I, common: F) -> Option<T>
+where
+    I: ParallelIterator<Item = T>,
+    T: Reducible,
+    F: Fn(T, T) -> Option<T> + Sync,
+{
+    #[cfg(feature = "profiling")]
+    let common = |a, b| {
+        profiling::record_call();
+        common(a, b)
+    };
+    // We need to use the `try_*` variants of fold/reduce so we can fail
+    // early when any two items don't have a common prefix/suffix.
+    iter.try_fold(
+        || None,
+        |previous, current| match previous {
+            Some(prev) => common(prev, current).map(Some),
+            None => Some(Some(current)),
+        },
+    )
+    .try_reduce(
+        || None,
+        |a, b| match (a, b) {
+            (Some(a), Some(b)) => common(a, b).map(Some),
+            (Some(common), None) | (None, Some(common)) => Some(Some(common)),
+            // Both sides were empty, which isn't the same as having nothing in common.
+            (None, None) => Some(None),
+        },
+    )
+    .flatten()
+    .filter(|result| !result.is_empty())
+}
+
+/// Values which [`reduce_by`] can reduce, all of which hold a (partial) common
+/// prefix/suffix that might be empty.
+trait Reducible: Send {
+    /// Returns whether the common prefix/suffix held by this value is empty.
+    fn is_empty(&self) -> bool;
+}
+
+impl Reducible for &str {
+    fn is_empty(&self) -> bool {
+        str::is_empty(self)
+    }
+}
+
+impl<T: Sync> Reducible for &[T] {
+    fn is_empty(&self) -> bool {
+        <[T]>::is_empty(self)
+    }
+}
+
+/// Some value along with a (partial) result.
+impl<A: Send, U: ?Sized> Reducible for (A, &U)
+where
+    for<'a> &'a U: Reducible,
+{
+    fn is_empty(&self) -> bool {
+        self.1.is_empty()
+    }
+}
+
+/// Some value along with the length of its common prefix/suffix.
+impl<A: Send> Reducible for (A, usize) {
+    fn is_empty(&self) -> bool {
+        self.1 == 0
+    }
+}
+
+/// A (partial) result along with the indices of the strings limiting it.
+impl Reducible for (&str, [usize; 2]) {
+    fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        Cancelled, CommonRaw, CommonStr, Finder, StringPrefix, common_line_prefix,
+        common_line_suffix, common_prefix_and_suffix_pair, common_prefix_bridge,
+        common_prefix_endpoints, common_prefix_from_fn, common_prefix_of, common_prefix_with_diff,
+        common_suffix_bridge, common_suffix_from_fn, common_suffix_of, compare_homogeneity,
+        reduce_common, verify_common_prefix, verify_common_prefix_raw, verify_common_suffix,
+        verify_common_suffix_raw,
+    };
+    use arrayvec::ArrayVec;
+    use rayon::prelude::*;
+    use smallvec::SmallVec;
+    use std::cmp::Ordering;
+    use std::collections::{BTreeSet, HashSet};
+    use std::hint::black_box;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering as AtomicOrdering};
+    use std::{iter, panic, ptr};
+    use ya_rand::*;
+
+    const BASE_LEN: usize = 19;
+    const COMMON: &str = "this is just a simple sentence";
+    const EXT_LEN: usize = 13;
+    const TOTAL_LEN: usize = BASE_LEN + EXT_LEN;
+    const VEC_LEN: usize = 1 << 15;
+
+    #[test]
+    fn str_prefix_sanity() {
+        let v = black_box(vec![COMMON.to_string(); BASE_LEN]);
+        let common = v.common_prefix_ref().unwrap();
+        assert_eq!(common, COMMON);
+        assert_eq!(common.len(), COMMON.len());
+    }
+
+    #[test]
+    fn str_suffix_sanity() {
+        let v = black_box(vec![COMMON.to_string(); BASE_LEN]);
+        let common = v.common_suffix_ref().unwrap();
+        assert_eq!(common, COMMON);
+        assert_eq!(common.len(), COMMON.len());
+    }
+
+    #[test]
+    fn raw_prefix_sanity() {
+        let v = black_box(vec![COMMON.as_bytes().to_vec(); BASE_LEN]);
+        let common = v.common_prefix_raw_ref().unwrap();
+        assert_eq!(common, COMMON.as_bytes());
+        assert_eq!(common.len(), COMMON.len());
+    }
+
+    #[test]
+    fn raw_suffix_sanity() {
+        let v = black_box(vec![COMMON.as_bytes().to_vec(); BASE_LEN]);
+        let common = v.common_suffix_raw_ref().unwrap();
+        assert_eq!(common, COMMON.as_bytes());
+        assert_eq!(common.len(), COMMON.len());
+    }
+
+    /// Rayon splits collections in half until its jobs are small enough, so the
+    /// task tree is only log N deep, while each job folds its piece in a loop.
+    /// Run with `cargo test --release -- --ignored` to check that this holds up.
+    #[test]
+    #[ignore = "allocates and scans 50M elements"]
+    fn huge() {
+        const HUGE_LEN: usize = 50_000_000;
+        let mut v = vec![[7u8, 1, 2, 3]; HUGE_LEN];
+        assert_eq!(v.common_prefix_raw_ref().unwrap(), [7, 1, 2, 3]);
+        v[HUGE_LEN - 1][1] = 0;
+        assert_eq!(v.common_prefix_raw_ref().unwrap(), [7]);
+        assert_eq!(v.common_suffix_raw_ref().unwrap(), [2, 3]);
+
+        let v = vec!["ab"; HUGE_LEN];
+        assert_eq!(v.common_prefix_ref().unwrap(), "ab");
+        assert_eq!(v.common_prefix_chunked(1 << 16).unwrap(), "ab");
+    }
+
+    #[test]
+    fn misc() {
+        let input: [String; 0] = [];
+        let prefix = input.common_prefix();
+        assert_eq!(prefix, None);
+        let suffix = input.common_suffix();
+        assert_eq!(suffix, None);
+
+        let input = ["just a single entry"];
+        let prefix = input.common_prefix().unwrap();
+        assert_eq!(prefix, input[0]);
+        let suffix = input.common_suffix().unwrap();
+        assert_eq!(suffix, input[0]);
+
+        let input = ["foobar", "fooqux", "foodle", "fookys"];
+        let prefix = input.common_prefix().unwrap();
+        assert_eq!(prefix, "foo");
+        let suffix = input.common_suffix();
+        assert_eq!(suffix, None);
+
+        let input = ["café", "caféine"];
+        let prefix = input.common_prefix().unwrap();
+        assert_eq!(prefix, "café");
+        let suffix = input.common_suffix();
+        assert_eq!(suffix, None);
+
+        let input = ["äbc", "âbc"];
+        let prefix = input.common_prefix();
+        assert_eq!(prefix, None);
+        let suffix = input.common_suffix().unwrap();
+        assert_eq!(suffix, "bc");
+
+        let input = ["abc€", "xyz€"];
+        let prefix = input.common_prefix();
+        assert_eq!(prefix, None);
+        let suffix = input.common_suffix().unwrap();
+        assert_eq!(suffix, "€");
+
+        let input = ["abcä", "defâ"];
+        let prefix = input.common_prefix();
+        assert_eq!(prefix, None);
+        let suffix = input.common_suffix();
+        assert_eq!(suffix, None);
+
+        let input = ["some thingy", "nothing"];
+        let prefix = input.common_prefix();
+        assert_eq!(prefix, None);
+        let suffix = input.common_suffix();
+        assert_eq!(suffix, None);
+
+        let input = ["-lol-", "_lol_"];
+        let prefix = input.common_prefix();
+        assert_eq!(prefix, None);
+        let suffix = input.common_suffix();
+        assert_eq!(suffix, None);
+
+        let input = ["a🤖b", "a🤡b"];
+        let prefix = input.common_prefix().unwrap();
+        assert_eq!(prefix, "a");
+        let suffix = input.common_suffix().unwrap();
+        assert_eq!(suffix, "b");
+
+        let input = ["résumé", "résister"];
+        let prefix = input.common_prefix().unwrap();
+        assert_eq!(prefix, "rés");
+        let suffix = input.common_suffix();
+        assert_eq!(suffix, None);
+
+        let input = ["abcédef", "xyzèdef"];
+        let prefix = input.common_prefix();
+        assert_eq!(prefix, None);
+        let suffix = input.common_suffix().unwrap();
+        assert_eq!(suffix, "def");
+
+        let input = ["Goodbye 👋", "Farewell 👋"];
+        let prefix = input.common_prefix();
+        assert_eq!(prefix, None);
+        let suffix = input.common_suffix().unwrap();
+        assert_eq!(suffix, " 👋");
+
+        let input = ["Family: 👨‍👩‍👧", "Group: 👨‍👩‍👧"];
+        let prefix = input.common_prefix();
+        assert_eq!(prefix, None);
+        let suffix = input.common_suffix().unwrap();
+        assert_eq!(suffix, ": 👨‍👩‍👧");
+
+        let input = ["just some words 世界", "世界"];
+        let prefix = input.common_prefix();
+        assert_eq!(prefix, None);
+        let suffix = input.common_suffix().unwrap();
+        assert_eq!(suffix, "世界");
+
+        let input = ["tests😀", "best😀"];
+        let prefix = input.common_prefix();
+        assert_eq!(prefix, None);
+        let suffix = input.common_suffix().unwrap();
+        assert_eq!(suffix, "😀");
+
+        let input = ["wowie_bruhther_clap", "wowie-lol-clap", "wowie_xd_clap"];
+        let prefix = input.common_prefix().unwrap();
+        assert_eq!(prefix, "wowie");
+        let suffix = input.common_suffix().unwrap();
+        assert_eq!(suffix, "clap");
+    }
+
+    /// Minimal custom collection, implementing only what rayon asks of any collection
+    /// which can be iterated over in parallel by reference.
+    struct Records {
+        names: Vec<String>,
+    }
+
+    impl<'a> IntoParallelIterator for &'a Records {
+        type Item = &'a String;
+        type Iter = rayon::slice::Iter<'a, String>;
+
+        fn into_par_iter(self) -> Self::Iter {
+            self.names.par_iter()
+        }
+    }
+
+    #[test]
+    fn custom_collection() {
+        let records = Records {
+            names: vec![
+                "record_one".into(),
+                "record_two".into(),
+                "record_three".into(),
+            ],
+        };
+        assert_eq!(records.par_iter().count(), 3);
+        assert_eq!(records.common_prefix().unwrap(), "record_");
+        assert_eq!(records.common_suffix(), None);
+        let raw: Option<&[u8]> = records.common_prefix_raw_ref();
+        assert_eq!(raw.unwrap(), b"record_");
+        // Methods requiring an indexed iterator work as well, since slice iterators are.
+        assert_eq!(records.common_prefix_borrowing_from(2).unwrap(), "record_");
+    }
+
+    #[test]
+    fn cross_collection() {
+        let before = vec!["prefix_a_suffix".to_string(), "prefix_b_suffix".to_string()];
+        let after = ["prefix_c_suffix", "prefix_d_suffix"];
+        assert_eq!(common_prefix_of(&before, &after).unwrap(), "prefix_");
+        assert_eq!(common_suffix_of(&before, &after).unwrap(), "_suffix");
+        assert_eq!(common_prefix_of(&after, &before).unwrap(), "prefix_");
+
+        let other = ["pre"];
+        assert_eq!(common_prefix_of(&before, &other).unwrap(), "pre");
+        assert_eq!(common_suffix_of(&before, &other), None);
+
+        let empty: [&str; 0] = [];
+        assert_eq!(common_prefix_of(&empty, &after).unwrap(), "prefix_");
+        assert_eq!(common_suffix_of(&before, &empty).unwrap(), "_suffix");
+        assert_eq!(common_prefix_of(&empty, &empty), None);
+
+        let input = vec![COMMON.to_string(); VEC_LEN];
+        let empty: Vec<String> = Vec::new();
+        assert_eq!(common_prefix_of(&empty, &input).unwrap(), COMMON);
+        assert_eq!(common_suffix_of(&input, &empty).unwrap(), COMMON);
+    }
+
+    #[test]
+    fn divergence() {
+        let input = ["foobar", "foobaz", "fooqux", "foo", "bar", "foobar_"];
+        assert_eq!(input.prefix_divergence(), [6, 5, 3, 3, 0, 6]);
+
+        let input = ["résumé", "résister", "rést"];
+        assert_eq!(input.prefix_divergence(), [8, 4, 4]);
+
+        let input: [String; 0] = [];
+        assert!(input.prefix_divergence().is_empty());
+
+        let input = vec![COMMON.to_string(); VEC_LEN];
+        let divergence = input.prefix_divergence();
+        assert_eq!(divergence.len(), VEC_LEN);
+        assert!(divergence.iter().all(|&i| i == COMMON.len()));
+    }
+
+    #[test]
+    fn sorted() {
+        let mut input = vec!["foo_c", "foo_a", "foo_bar", "foo_b", "foo_"];
+        input.sort_unstable();
+        assert_eq!(input.common_prefix_sorted().unwrap(), "foo_");
+
+        let input = ["résister", "résumé"];
+        assert_eq!(input.common_prefix_sorted().unwrap(), "rés");
+
+        let input = ["abc", "xyz"];
+        assert_eq!(input.common_prefix_sorted(), None);
+
+        let input = ["single"];
+        assert_eq!(input.common_prefix_sorted().unwrap(), "single");
+
+        let input: [&str; 0] = [];
+        assert_eq!(input.common_prefix_sorted(), None);
+
+        let mut rng = new_rng();
+        let base = new_string_with::<BASE_LEN, _>(|| random_char(&mut rng));
+        let mut strings: Vec<_> = (0..VEC_LEN)
+            .map(|_| base.clone() + &new_string_with::<EXT_LEN, _>(|| random_char(&mut rng)))
+            .collect();
+        strings.sort_unstable();
+        assert_eq!(strings.common_prefix_sorted().unwrap(), base);
+    }
+
+    #[test]
+    fn shortest_first() {
+        let input = ["/usr/lib/a", "/usr/lib", "/usr/lib/b/c"];
+        assert_eq!(input.common_prefix_shortest_first().unwrap(), "/usr/lib");
+        let input = ["/usr/lib/a", "/usr/bin", "/usr/lib/b/c"];
+        assert_eq!(input.common_prefix_shortest_first().unwrap(), "/usr/");
+        let input = ["", "abc"];
+        assert_eq!(input.common_prefix_shortest_first(), None);
+        let input = ["abc", "xyz"];
+        assert_eq!(input.common_prefix_shortest_first(), None);
+        let input: [&str; 0] = [];
+        assert_eq!(input.common_prefix_shortest_first(), None);
+
+        let mut input: Vec<String> = (0..VEC_LEN).map(|i| format!("{COMMON}/{i}")).collect();
+        input.push(COMMON.to_string());
+        assert_eq!(input.common_prefix_shortest_first().unwrap(), COMMON);
+        input.push("this is not".to_string());
+        assert_eq!(
+            input.common_prefix_shortest_first(),
+            input.common_prefix_ref()
+        );
+    }
+
+    #[test]
+    fn dedup() {
+        let mut rng = new_rng();
+        let base = new_string_with::<BASE_LEN, _>(|| random_char(&mut rng));
+        let unique: Vec<String> = (0..100)
+            .map(|_| {
+                let ext = new_string_with::<EXT_LEN, _>(|| random_char(&mut rng));
+                format!("{base}{ext}")
+            })
+            .collect();
+        let input: Vec<String> = (0..VEC_LEN).map(|i| unique[i % 100].clone()).collect();
+        assert_eq!(input.common_prefix_dedup(), input.common_prefix());
+        let input = vec![COMMON.to_string(); VEC_LEN];
+        assert_eq!(input.common_prefix_dedup().unwrap(), COMMON);
+        let input = ["a", "b", "a"];
+        assert_eq!(input.common_prefix_dedup(), None);
+        let input: [&str; 0] = [];
+        assert_eq!(input.common_prefix_dedup(), None);
+    }
+
+    #[test]
+    fn coverage_tiers() {
+        let input = [
+            "app/src/main.rs",
+            "app/src/lib.rs",
+            "app/src/lib/mod.rs",
+            "app/tests/it.rs",
+            "docs/index.md",
+        ];
+        let tiers = input.prefix_coverage_tiers(&[1.0, 0.8, 0.6, 0.4, 0.0, 1.5]);
+        let expected = [
+            (1.0, None),
+            (0.8, Some("app/")),
+            (0.6, Some("app/src/")),
+            (0.4, Some("app/src/lib")),
+            (0.0, Some("app/src/lib/mod.rs")),
+            (1.5, None),
+        ];
+        assert_eq!(tiers, expected.map(|(t, p)| (t, p.map(String::from))));
+        // Equally long prefixes, of which the smaller one wins.
+        let input = ["b1", "b2", "a1", "a2"];
+        assert_eq!(
+            input.prefix_coverage_tiers(&[0.5])[0].1.as_deref(),
+            Some("a")
+        );
+        let input: [&str; 0] = [];
+        assert_eq!(input.prefix_coverage_tiers(&[0.5]), [(0.5, None)]);
+
+        let mut input: Vec<String> = (0..VEC_LEN).map(|i| format!("{COMMON}{i}")).collect();
+        input.push("outlier".into());
+        let tiers = input.prefix_coverage_tiers(&[1.0, 0.99]);
+        assert_eq!(tiers[0].1, input.common_prefix());
+        assert_eq!(tiers[1].1.as_deref(), Some(COMMON));
+
+        // Exactly 7% of the strings, even though `0.07 * 100.0` is slightly above 7.
+        let mut input: Vec<String> = (0..93).map(|i| format!("{i:02}")).collect();
+        input.extend((0..7).map(|i| format!("seven_{i}")));
+        let tiers = input.prefix_coverage_tiers(&[0.07, 0.08]);
+        assert_eq!(tiers[0].1.as_deref(), Some("seven_"));
+        assert_ne!(tiers[1].1.as_deref(), Some("seven_"));
+    }
+
+    #[test]
+    fn of_longest() {
+        let input = ["ab", "template_one", "x", "template_two", "templ"];
+        assert_eq!(input.common_prefix_of_longest(2).unwrap(), "template_");
+        assert_eq!(input.common_prefix_of_longest(3).unwrap(), "templ");
+        assert_eq!(input.common_prefix_of_longest(4), None);
+        assert_eq!(input.common_prefix_of_longest(5), input.common_prefix_ref());
+        assert_eq!(input.common_prefix_of_longest(usize::MAX), None);
+        assert_eq!(input.common_prefix_of_longest(1).unwrap(), "template_one");
+        assert_eq!(input.common_prefix_of_longest(0), None);
+        let input: [&str; 0] = [];
+        assert_eq!(input.common_prefix_of_longest(3), None);
+
+        let mut input: Vec<String> = (0..VEC_LEN).map(|i| format!("junk{i}")).collect();
+        input.extend((0..100).map(|i| format!("{COMMON}{i:03}")));
+        let mut rng = new_rng();
+        shuffle(&mut rng, &mut input);
+        let expected = format!("{COMMON}0");
+        assert_eq!(input.common_prefix_of_longest(100).unwrap(), expected);
+        assert_eq!(input.common_prefix_of_longest(101), None);
+    }
+
+    #[test]
+    fn witnesses() {
+        let input = ["interstellar", "internet", "interval", "in", "internal"];
+        let (prefix, [i, j]) = input.prefix_witnesses().unwrap();
+        assert_eq!(prefix, "in");
+        assert_eq!(StringPrefix::common(input[i], input[j]).unwrap(), prefix);
+        let input = ["single"];
+        assert_eq!(input.prefix_witnesses().unwrap(), ("single".into(), [0, 0]));
+        let input = ["abc", "abd", "xyz"];
+        assert_eq!(input.prefix_witnesses(), None);
+        let input: [&str; 0] = [];
+        assert_eq!(input.prefix_witnesses(), None);
+        assert_eq!([""].prefix_witnesses(), None);
+
+        let mut rng = new_rng();
+        for _ in 0..8 {
+            let mut input: Vec<String> = (0..VEC_LEN)
+                .map(|_| {
+                    let ext = new_string_with::<EXT_LEN, _>(|| random_char(&mut rng));
+                    format!("{COMMON}{COMMON}{ext}")
+                })
+                .collect();
+            // Cut one string short somewhere, sometimes in the middle of a char.
+            let victim = rng.bound(VEC_LEN as u64) as usize;
+            let cut = COMMON.len() + rng.bound(COMMON.len() as u64) as usize;
+            input[victim].replace_range(cut.., "\u{1F600}");
+            let (prefix, [i, j]) = input.prefix_witnesses().unwrap();
+            assert_eq!(Some(prefix.as_str()), input.common_prefix_ref());
+            assert_eq!(StringPrefix::common(&input[i], &input[j]).unwrap(), prefix);
+        }
+    }
+
+    #[test]
+    fn borrowing_from() {
+        let input = [
+            "shared_a_end".to_string(),
+            "shared_bb_end".into(),
+            "shared_end".into(),
+        ];
+        for (i, s) in input.iter().enumerate() {
+            let prefix = input.common_prefix_borrowing_from(i).unwrap();
+            assert_eq!(prefix, "shared_");
+            assert!(ptr::eq(prefix.as_ptr(), s.as_ptr()));
+            let suffix = input.common_suffix_borrowing_from(i).unwrap();
+            assert_eq!(suffix, "_end");
+            assert!(ptr::eq(suffix.as_ptr(), s[s.len() - 4..].as_ptr()));
+        }
+        assert_eq!(input.common_prefix_borrowing_from(3), None);
+        assert_eq!(input.common_suffix_borrowing_from(usize::MAX), None);
+        let input = ["abc", "xyz"];
+        assert_eq!(input.common_prefix_borrowing_from(0), None);
+        let input: Vec<String> = Vec::new();
+        assert_eq!(input.common_prefix_borrowing_from(0), None);
+
+        // Elements which shrink between passes still give slices of themselves.
+        let input = [Shrinking::new(1), Shrinking::new(1)];
+        let prefix = input.common_prefix_borrowing_from(0);
+        assert!(prefix.is_none_or(|prefix| "abcdefgh".starts_with(prefix)));
+        let input = [Shrinking::new(1), Shrinking::new(1)];
+        let suffix = input.common_suffix_borrowing_from(1);
+        assert!(suffix.is_none_or(|suffix| "abcdefgh".ends_with(suffix)));
+    }
+
+    #[test]
+    fn with_diff() {
+        assert_eq!(
+            common_prefix_with_diff("branch_a", "branch_b"),
+            (Some("branch_"), Some((b'a', b'b')))
+        );
+        assert_eq!(
+            common_prefix_with_diff("abc", "xyz"),
+            (None, Some((b'a', b'x')))
+        );
+        // Neither string differs before the shorter one ends.
+        assert_eq!(
+            common_prefix_with_diff("tree", "treehouse"),
+            (Some("tree"), None)
+        );
+        assert_eq!(
+            common_prefix_with_diff("treehouse", "tree"),
+            (Some("tree"), None)
+        );
+        assert_eq!(
+            common_prefix_with_diff("same", "same"),
+            (Some("same"), None)
+        );
+        assert_eq!(common_prefix_with_diff("", "abc"), (None, None));
+        // 'é' and 'è' share their first byte, so they differ in their second.
+        assert_eq!(
+            common_prefix_with_diff("xé", "xè"),
+            (Some("x"), Some((0xA9, 0xA8)))
+        );
+
+        let a = format!("{COMMON}{COMMON}!");
+        let b = format!("{COMMON}{COMMON}?");
+        let expected = format!("{COMMON}{COMMON}");
+        assert_eq!(
+            common_prefix_with_diff(&a, &b),
+            (Some(expected.as_str()), Some((b'!', b'?')))
+        );
+    }
+
+    #[test]
+    fn from_fn() {
+        // Names and cities of every record, interleaved in one buffer.
+        let fields = ["user_ann", "paris", "user_bob", "oslo", "user_cy", "lima"];
+        let names = common_prefix_from_fn(fields.len() / 2, |i| fields[i * 2]);
+        assert_eq!(names.unwrap(), "user_");
+        let cities = common_suffix_from_fn(fields.len() / 2, |i| fields[i * 2 + 1]);
+        assert_eq!(cities, None);
+        assert_eq!(common_prefix_from_fn(0, |_| unreachable!()), None);
+
+        let input: Vec<String> = (0..VEC_LEN).map(|i| format!("{i}{COMMON}")).collect();
+        let suffix = common_suffix_from_fn(input.len(), |i| &input[i]);
+        assert_eq!(suffix, input.common_suffix_ref());
+    }
+
+    #[test]
+    fn raw_from_shortest() {
+        let input = [
+            b"blob_header_long_body".to_vec(),
+            b"blob_header_b".to_vec(),
+            b"blob_head".to_vec(),
+            b"blob_heap".to_vec(),
+        ];
+        let prefix = input.common_prefix_raw_from_shortest().unwrap();
+        assert_eq!(prefix, b"blob_hea");
+        // Both of the shortest blobs are equally short, so it's the first of them.
+        assert!(ptr::eq(prefix.as_ptr(), input[2].as_ptr()));
+        let input = [vec![1, 2], vec![3]];
+        assert_eq!(input.common_prefix_raw_from_shortest(), None);
+        let input: [Vec<u8>; 0] = [];
+        assert_eq!(input.common_prefix_raw_from_shortest(), None);
+
+        let mut input: Vec<Vec<u8>> = (0..VEC_LEN)
+            .map(|i| format!("{COMMON}{i:05}").into_bytes())
+            .collect();
+        input.insert(VEC_LEN / 2, COMMON.as_bytes().to_vec());
+        let prefix = input.common_prefix_raw_from_shortest().unwrap();
+        assert_eq!(prefix, COMMON.as_bytes());
+        assert!(ptr::eq(prefix.as_ptr(), input[VEC_LEN / 2].as_ptr()));
+
+        // Elements which shrink between passes still give a slice of the shortest one.
+        let input = [Shrinking::new(1), Shrinking::new(1)];
+        let prefix: Option<&[u8]> = input.common_prefix_raw_from_shortest();
+        assert!(prefix.is_none_or(|prefix| b"abcdefgh".starts_with(prefix)));
+    }
+
+    #[test]
+    fn raw_until() {
+        let input = [
+            b"name_a\0garbage".to_vec(),
+            b"name_b\0".to_vec(),
+            b"name_a\0\0".to_vec(),
+        ];
+        assert_eq!(input.common_prefix_raw_until(0).unwrap(), b"name_");
+        let input = [b"abc\0xyz".to_vec(), b"abc\0xyz".to_vec()];
+        assert_eq!(input.common_prefix_raw_until(0).unwrap(), b"abc");
+        assert_eq!(input.common_prefix_raw_ref().unwrap(), b"abc\0xyz");
+        // Without the sentinel, elements are compared in full.
+        let input = [b"abc".to_vec(), b"abcd\0".to_vec()];
+        assert_eq!(input.common_prefix_raw_until(0).unwrap(), b"abc");
+        let input = [b"\0abc".to_vec(), b"\0abc".to_vec()];
+        assert_eq!(input.common_prefix_raw_until(0), None);
+        let input = [vec![1u32, 2, 9, 3], vec![1, 2, 9]];
+        assert_eq!(input.common_prefix_raw_until(9).unwrap(), [1, 2]);
+        // A single element starting with the sentinel has nothing before it.
+        let input = [b"\0abc".to_vec()];
+        assert_eq!(input.common_prefix_raw_until(0), None);
+    }
+
+    #[test]
+    fn verify() {
+        let mut rng = new_rng();
+        let input: Vec<String> = (0..VEC_LEN)
+            .map(|_| {
+                let ext = new_string_with::<EXT_LEN, _>(|| random_char(&mut rng));
+                format!("{COMMON}{ext}{COMMON}")
+            })
+            .collect();
+        let prefix = input.common_prefix().unwrap();
+        let suffix = input.common_suffix().unwrap();
+        assert!(verify_common_prefix(&input, &prefix));
+        assert!(verify_common_suffix(&input, &suffix));
+        assert!(verify_common_prefix(&input, "this"));
+        assert!(verify_common_prefix(&input, ""));
+        assert!(!verify_common_prefix(&input, "that"));
+        assert!(!verify_common_suffix(&input, "sentence!"));
+        let empty: [&str; 0] = [];
+        assert!(verify_common_prefix(&empty, "anything"));
+        assert!(empty.all_start_with("anything"));
+        assert!(empty.all_end_with("anything"));
+        assert!(input.all_start_with(&prefix));
+        assert!(!input.all_start_with(&format!("{prefix}!")));
+        assert!(input.all_end_with(&suffix));
+        assert!(!input.all_end_with(&input[0]));
+
+        let input = [vec![1u16, 2, 3], vec![1, 2, 4, 3]];
+        assert!(verify_common_prefix_raw(&input, &[1, 2]));
+        assert!(!verify_common_prefix_raw(&input, &[1, 2, 3]));
+        assert!(verify_common_suffix_raw(&input, &[3]));
+        assert!(!verify_common_suffix_raw(&input, &[2, 3]));
+    }
+
+    #[test]
+    fn stripped_iter() {
+        let input = ["src/lib.rs", "src/finder.rs", "src/"];
+        let tails: Vec<&str> = input.prefix_stripped_iter().unwrap().collect();
+        assert_eq!(tails, ["lib.rs", "finder.rs", ""]);
+        let input = ["réservé.txt", "résumé.txt"];
+        let tails: Vec<&str> = input.prefix_stripped_iter().unwrap().collect();
+        assert_eq!(tails, ["ervé.txt", "umé.txt"]);
+        let heads: Vec<&str> = input.suffix_stripped_iter().unwrap().collect();
+        assert_eq!(heads, ["réserv", "résum"]);
+        let input = ["abc", "xyz"];
+        assert!(input.prefix_stripped_iter().is_none());
+        assert!(input.suffix_stripped_iter().is_none());
+        let input: [&str; 0] = [];
+        assert!(input.prefix_stripped_iter().is_none());
+        assert!([""].prefix_stripped_iter().is_none());
+        assert!([""].suffix_stripped_iter().is_none());
+
+        let input: Vec<String> = (0..VEC_LEN).map(|i| format!("{COMMON}{i}")).collect();
+        let total: usize = input.prefix_stripped_iter().unwrap().map(str::len).sum();
+        let expected: usize = input.iter().map(|s| s.len() - COMMON.len()).sum();
+        assert_eq!(total, expected);
+
+        // Strings which shrink after the prefix/suffix was found can't be sliced.
+        let input = [Shrinking::new(1), Shrinking::new(1)];
+        let stripped = panic::catch_unwind(|| input.prefix_stripped_iter().unwrap().count());
+        assert!(stripped.is_err());
+        let input = [Shrinking::new(1), Shrinking::new(1)];
+        let stripped = panic::catch_unwind(|| input.suffix_stripped_iter().unwrap().count());
+        assert!(stripped.is_err());
+    }
+
+    #[test]
+    fn ascii() {
+        let input = ["prefix_one", "prefix_two", "prefix_three"];
+        assert_eq!(unsafe { input.common_prefix_ascii() }.unwrap(), "prefix_");
+        let input = ["same", "same"];
+        assert_eq!(unsafe { input.common_prefix_ascii() }.unwrap(), "same");
+        let input = ["abc", "xyz"];
+        assert_eq!(unsafe { input.common_prefix_ascii() }, None);
+
+        let mut rng = new_rng();
+        let input: Vec<String> = (0..VEC_LEN)
+            .map(|_| {
+                let ext = new_string_with::<EXT_LEN, _>(|| (b'a' + rng.bound(26) as u8) as char);
+                format!("{COMMON}{ext}")
+            })
+            .collect();
+        assert_eq!(
+            unsafe { input.common_prefix_ascii() },
+            input.common_prefix()
+        );
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic]
+    fn ascii_checked() {
+        let input = ["caf\u{e9}", "caf\u{e8}"];
+        let _ = unsafe { input.common_prefix_ascii() };
+    }
+
+    #[test]
+    fn sampled() {
+        let input: Vec<String> = (0..VEC_LEN).map(|i| format!("{COMMON}{i:05}")).collect();
+        let exact = input.common_prefix().unwrap();
+        for seed in 0..8 {
+            let estimate = input.common_prefix_sampled(100, seed).unwrap();
+            assert!(estimate.starts_with(&exact));
+            assert_eq!(input.common_prefix_sampled(100, seed).unwrap(), estimate);
+        }
+        assert_eq!(input.common_prefix_sampled(VEC_LEN, 7).unwrap(), exact);
+        assert_eq!(input.common_prefix_sampled(usize::MAX, 7).unwrap(), exact);
+        // A single sampled string is its own common prefix.
+        let estimate = input.common_prefix_sampled(1, 3).unwrap();
+        assert!(input.contains(&estimate));
+        assert_eq!(input.common_prefix_sampled(0, 0), None);
+        let input: [&str; 0] = [];
+        assert_eq!(input.common_prefix_sampled(10, 0), None);
+    }
+
+    #[test]
+    fn with_cost() {
+        // 4 equal bytes and the one which differs.
+        let input = ["abcdX", "abcdY"];
+        assert_eq!(input.common_prefix_with_cost(), (Some("abcd"), 5));
+        // Nothing past the end of the shorter string gets compared.
+        let input = ["abc", "abcdef"];
+        assert_eq!(input.common_prefix_with_cost(), (Some("abc"), 3));
+        let input = ["xyz", "abc"];
+        assert_eq!(input.common_prefix_with_cost(), (None, 1));
+        let input = ["alone"];
+        assert_eq!(input.common_prefix_with_cost(), (Some("alone"), 0));
+
+        let input: Vec<String> = (0..VEC_LEN).map(|i| format!("{COMMON}{i:05}")).collect();
+        let (prefix, cost) = input.common_prefix_with_cost();
+        assert_eq!(prefix, input.common_prefix_ref());
+        // Every comparison covers at least the shared part.
+        let shared = prefix.unwrap().len() as u64;
+        assert!(cost >= (VEC_LEN as u64 - 1) * shared);
+        assert!(cost <= (VEC_LEN as u64 - 1) * (COMMON.len() as u64 + 5));
+    }
+
+    #[test]
+    fn word() {
+        let input = ["hello wor", "hello wonder"];
+        assert_eq!(input.common_prefix_word().unwrap(), "hello ");
+        let input = ["hello", "hello there"];
+        assert_eq!(input.common_prefix_word().unwrap(), "hello");
+        let input = ["one two\tthree", "one two\tthr", "one two\tthreefold"];
+        assert_eq!(input.common_prefix_word().unwrap(), "one two\t");
+        let input = ["same words", "same words"];
+        assert_eq!(input.common_prefix_word().unwrap(), "same words");
+        let input = ["sentence", "sentinel"];
+        assert_eq!(input.common_prefix_word(), None);
+        let input = ["abc", "xyz"];
+        assert_eq!(input.common_prefix_word(), None);
+
+        let input: Vec<String> = (0..VEC_LEN).map(|i| format!("{COMMON} {i}")).collect();
+        assert_eq!(input.common_prefix_word().unwrap(), format!("{COMMON} "));
+    }
+
+    #[test]
+    fn lines() {
+        let text = "    let a = 1;\n    let b = 2;\r\n    a + b\n";
+        assert_eq!(common_line_prefix(text).unwrap(), "    ");
+        assert_eq!(common_line_suffix("ab;\ncd;\r\n").unwrap(), ";");
+        assert_eq!(common_line_prefix("single line").unwrap(), "single line");
+        assert_eq!(common_line_prefix("abc\n\nabd"), None);
+        assert_eq!(common_line_suffix("abc\n\nxbc"), None);
+        assert_eq!(common_line_prefix("\n"), None);
+        assert_eq!(common_line_prefix(""), None);
+
+        let mut rng = new_rng();
+        let input: Vec<String> = (0..VEC_LEN)
+            .map(|_| {
+                // A random newline would split the string into two lines.
+                let ext = new_string_with::<EXT_LEN, _>(|| {
+                    loop {
+                        match random_char(&mut rng) {
+                            '\n' => continue,
+                            c => break c,
+                        }
+                    }
+                });
+                format!("{COMMON}{ext}{COMMON}")
+            })
+            .collect();
+        let text = input.join("\n");
+        assert_eq!(common_line_prefix(&text), input.common_prefix());
+        assert_eq!(common_line_suffix(&text), input.common_suffix());
+    }
+
+    #[test]
+    fn endpoints() {
+        let input = ["carpet", "car", "cartoon", "carbon", "cargo"];
+        let (min, max) = input.lexicographic_bounds().unwrap();
+        assert_eq!((min, max), ("car", "cartoon"));
+        assert_eq!(common_prefix_endpoints(min, max).unwrap(), "car");
+        let input = ["b", "a", "c"];
+        let (min, max) = input.lexicographic_bounds().unwrap();
+        assert_eq!(common_prefix_endpoints(min, max), None);
+        let input = ["only"];
+        assert_eq!(input.lexicographic_bounds().unwrap(), ("only", "only"));
+        let input: [&str; 0] = [];
+        assert_eq!(input.lexicographic_bounds(), None);
+
+        let mut rng = new_rng();
+        let mut input: Vec<String> = (0..VEC_LEN)
+            .map(|_| {
+                let ext = new_string_with::<EXT_LEN, _>(|| random_char(&mut rng));
+                format!("{COMMON}{ext}")
+            })
+            .collect();
+        shuffle(&mut rng, &mut input);
+        let (min, max) = input.lexicographic_bounds().unwrap();
+        assert_eq!(common_prefix_endpoints(min, max), input.common_prefix_ref());
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic]
+    fn sorted_unsorted() {
+        let input = ["abc", "zzz", "abd"];
+        let _ = input.common_prefix_sorted();
+    }
+
+    #[test]
+    fn short_circuit() {
+        // Nothing is shared by the first two strings, which is enough to stop
+        // every job from looking at the rest of the collection. With a single
+        // thread, jobs run in order, so none of them get started before that.
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(1)
+            .build()
+            .unwrap();
+        let mut input = vec![COMMON.to_string(); VEC_LEN * 8];
+        input[1].insert(0, '!');
+        let visited = AtomicUsize::new(0);
+        let iter = input.par_iter().map(|s| {
+            visited.fetch_add(1, AtomicOrdering::Relaxed);
+            s.as_str()
+        });
+        assert_eq!(
+            pool.install(|| reduce_common::<StringPrefix, _, _>(iter)),
+            None
+        );
+        assert_eq!(visited.into_inner(), 2);
+    }
+
+    #[test]
+    fn iterators() {
+        let input = ["a🤖b", "a🤖c"];
+        let chars: Vec<_> = input.common_prefix_chars().unwrap().collect();
+        assert_eq!(chars, ['a', '🤖']);
+        assert!(input.common_suffix_chars().is_none());
+
+        let input = [vec![1u32, 2, 3, 4], vec![0, 2, 3, 4]];
+        let elements: Vec<_> = input.common_suffix_raw_iter().unwrap().copied().collect();
+        assert_eq!(elements, [2, 3, 4]);
+        assert!(input.common_prefix_raw_iter().is_none());
+    }
+
+    #[test]
+    fn chunked() {
+        let mut rng = new_rng();
+        let base = new_string_with::<BASE_LEN, _>(|| random_char(&mut rng));
+        let strings: Vec<_> = (0..VEC_LEN)
+            .map(|_| {
+                let ext = new_string_with::<EXT_LEN, _>(|| random_char(&mut rng));
+                format!("{base}{ext}{base}")
+            })
+            .collect();
+        for chunk in [0, 1, 7, 1 << 10, VEC_LEN, VEC_LEN * 2] {
+            assert_eq!(strings.common_prefix_chunked(chunk).unwrap(), base);
+            assert_eq!(strings.common_suffix_chunked(chunk).unwrap(), base);
+        }
+
+        let input = ["foobar", "fooqux", "foodle", "fookys"];
+        assert_eq!(input.common_prefix_chunked(2).unwrap(), "foo");
+        assert_eq!(input.common_suffix_chunked(2), None);
+    }
+
+    #[test]
+    fn suffix_split_char() {
+        // The shorter string's length lands inside the longer one's leading '€'.
+        let input = ["€bc", "xbc"];
+        assert_eq!(input.common_suffix().unwrap(), "bc");
+        let input = ["xbc", "€bc"];
+        assert_eq!(input.common_suffix().unwrap(), "bc");
+
+        // Every compared byte past the split point is equal, but they only
+        // make up the tail of a char, so there is nothing valid to return.
+        let input = ["x€", "Ⴌ"];
+        assert_eq!(input.common_suffix(), None);
+        let input = ["Ⴌ", "x€"];
+        assert_eq!(input.common_suffix(), None);
+
+        // Same as above, but long enough for the chunked comparison to kick in.
+        let tail = "0123456789abcdefghijklmnopqrstuv";
+        let a = format!("{}€{tail}", "€".repeat(8));
+        let b = format!("Ⴌ{tail}");
+        let input = [a.clone(), b.clone()];
+        assert_eq!(input.common_suffix().unwrap(), tail);
+        let input = [b, a];
+        assert_eq!(input.common_suffix().unwrap(), tail);
+    }
+
+    #[test]
+    fn emoji_boundary() {
+        // Shift the shared region through every offset of a 64 byte chunk,
+        // so the mismatch lands on each byte of the emoji in every position.
+        for pad in 0..=2 * 64 {
+            let pad = "p".repeat(pad);
+            let shared = format!("{pad}ab");
+            let cases = [
+                (format!("{shared}😀"), shared.clone()),
+                (format!("{shared}😀x"), format!("{shared}😁x")),
+                (format!("{shared}😀"), format!("{shared}🤀")),
+            ];
+            for (a, b) in cases {
+                for input in [[a.clone(), b.clone()], [b.clone(), a.clone()]] {
+                    assert_eq!(input.common_prefix_ref().unwrap(), shared);
+                    let mirrored = input.map(|s| s.chars().rev().collect::<String>());
+                    let suffix: String = shared.chars().rev().collect();
+                    assert_eq!(mirrored.common_suffix_ref().unwrap(), suffix);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn raw_byte_len() {
+        let input = ["foobar", "fooqux"];
+        assert_eq!(
+            input.common_prefix_raw_byte_len(),
+            input.common_prefix_len()
+        );
+        assert_eq!(input.common_suffix_raw_byte_len(), None);
+
+        // 'ä' and 'â' share their leading byte, 'é' and 'è' share theirs.
+        let input = ["äbc", "âbc"];
+        assert_eq!(input.common_prefix_len(), None);
+        assert_eq!(input.common_prefix_raw_byte_len().unwrap().get(), 1);
+
+        let input = ["résumé", "résister", "rés"];
+        assert_eq!(input.common_prefix_len().unwrap().get(), 4);
+        assert_eq!(input.common_prefix_raw_byte_len().unwrap().get(), 4);
+
+        let input = ["abcé", "xyzè"];
+        assert_eq!(input.common_suffix_len(), None);
+        assert_eq!(input.common_suffix_raw_byte_len(), None);
+
+        // '€' (e2 82 ac) and 'Ⴌ' (e1 82 ac) share their trailing bytes.
+        let input = ["1€", "2Ⴌ"];
+        assert_eq!(input.common_suffix_len(), None);
+        assert_eq!(input.common_suffix_raw_byte_len().unwrap().get(), 2);
+    }
+
+    #[test]
+    fn bytes_unchecked() {
+        let input = ["foobar", "fooqux"];
+        assert_eq!(input.common_prefix_bytes_unchecked().unwrap(), b"foo");
+        assert_eq!(input.common_suffix_bytes_unchecked(), None);
+
+        let input = ["1€", "2Ⴌ", "3€"];
+        let suffix = input.common_suffix_bytes_unchecked().unwrap();
+        assert_eq!(suffix, &"€".as_bytes()[1..]);
+        assert!(std::str::from_utf8(suffix).is_err());
+        assert_eq!(input.common_suffix(), None);
+
+        let input = ["äbc", "âbc"];
+        assert_eq!(
+            input.common_prefix_bytes_unchecked().unwrap(),
+            &"ä".as_bytes()[..1]
+        );
+        assert_eq!(input.common_suffix_bytes_unchecked().unwrap(), b"bc");
+
+        let tail = "0123456789abcdefghijklmnopqrstuv";
+        let input = [format!("{}€{tail}", "€".repeat(8)), format!("Ⴌ{tail}")];
+        let suffix = input.common_suffix_bytes_unchecked().unwrap();
+        assert_eq!(suffix.len(), tail.len() + 2);
+        assert!(suffix.ends_with(tail.as_bytes()));
+    }
+
+    #[test]
+    fn borrows_from_first() {
+        // Equal strings in separate allocations, so any of them could be the source.
+        let input = vec![COMMON.to_string(); VEC_LEN];
+        let first = input[0].as_str();
+        for _ in 0..1000 {
+            assert!(ptr::eq(input.common_prefix_ref().unwrap(), first));
+            assert!(ptr::eq(input.common_suffix_ref().unwrap(), first));
+        }
+    }
+
+    #[test]
+    fn single_empty() {
+        // A lone empty element has nothing in common, same as an empty result would.
+        let input = [String::new()];
+        assert!(input.common_prefix_ref().is_none());
+        assert!(input.common_suffix_ref().is_none());
+        assert!(input.common_prefix_len().is_none());
+        assert!(input.common_suffix_len().is_none());
+        let input: [Vec<u8>; 1] = [Vec::new()];
+        assert!(input.common_prefix_raw_ref().is_none());
+        assert!(input.common_suffix_raw_ref().is_none());
+        assert!(common_line_prefix("\n").is_none());
+        assert!(["abc"].common_prefix_from(3).is_none());
+        // A lone non-empty element is still its own common prefix/suffix.
+        let input = [COMMON];
+        assert!(ptr::eq(input.common_prefix_ref().unwrap(), COMMON));
+        assert!(ptr::eq(input.common_suffix_ref().unwrap(), COMMON));
+    }
+
+    #[test]
+    fn identical() {
+        let shared: Arc<str> = Arc::from(COMMON);
+        let input = vec![Arc::clone(&shared); VEC_LEN];
+        let prefix = input.common_prefix_ref().unwrap();
+        assert!(ptr::eq(prefix, &*shared));
+        let suffix = input.common_suffix_ref().unwrap();
+        assert!(ptr::eq(suffix, &*shared));
+
+        let mut input = input;
+        input.push(Arc::from("this is just a different sentence"));
+        assert_eq!(input.common_prefix_ref().unwrap(), "this is just a ");
+        assert_eq!(input.common_suffix_ref().unwrap(), " sentence");
+    }
+
+    #[test]
+    fn exact_mask() {
+        let input = ["key", "key_a", "key", "key_b"];
+        let (prefix, mask) = input.common_prefix_with_exact_mask().unwrap();
+        assert_eq!(prefix, "key");
+        assert_eq!(mask, [true, false, true, false]);
+        let input = ["key_a", "key_b"];
+        assert_eq!(input.common_prefix_with_exact_mask().unwrap().1, [false; 2]);
+        assert!(["a", "b"].common_prefix_with_exact_mask().is_none());
+
+        let mut rng = new_rng();
+        let mut input: Vec<String> = (0..VEC_LEN)
+            .map(|_| match rng.bits(1) {
+                0 => COMMON.to_string(),
+                _ => COMMON.to_string() + &new_string_with::<EXT_LEN, _>(|| random_char(&mut rng)),
+            })
+            .collect();
+        // Make sure nothing past `COMMON` is shared.
+        input.push(COMMON.to_string() + "\0");
+        input.push(COMMON.to_string() + "\u{1}");
+        let (prefix, mask) = input.common_prefix_with_exact_mask().unwrap();
+        assert_eq!(prefix, COMMON);
+        assert!(
+            mask.iter()
+                .zip(&input)
+                .all(|(&exact, s)| exact == (s == COMMON))
+        );
+    }
+
+    #[test]
+    fn indexed() {
+        let input = ["shared_one", "shared_two_ext", "shared_three"].map(String::from);
+        let (i, prefix) = input.common_prefix_ref_indexed().unwrap();
+        assert_eq!(prefix, "shared_");
+        assert!(input[i].as_ptr() == prefix.as_ptr());
+        assert!(input.common_suffix_ref_indexed().is_none());
 
-    /// Returns the length of the longest common prefix of all referenced data.
-    ///
-    /// Returns `None` instead of 0 when there is no common prefix.
-    fn common_prefix_raw_len(&self) -> Option<NonZeroUsize> {
-        self.common_prefix_raw_ref()
-            .map(|s| unsafe { NonZeroUsize::new_unchecked(s.len()) })
+        let input = vec![String::from("a_end"), String::from("bb_end")];
+        let (i, suffix) = input.common_suffix_ref_indexed().unwrap();
+        assert_eq!(suffix, "_end");
+        assert!(
+            input[i]
+                .as_bytes()
+                .as_ptr_range()
+                .contains(&suffix.as_ptr())
+        );
+
+        let input: Vec<String> = Vec::new();
+        assert!(input.common_prefix_ref_indexed().is_none());
     }
 
-    /// Returns the length of the longest common suffix of all referenced data.
-    ///
-    /// Returns `None` instead of 0 when there is no common suffix.
-    fn common_suffix_raw_len(&self) -> Option<NonZeroUsize> {
-        self.common_suffix_raw_ref()
-            .map(|s| unsafe { NonZeroUsize::new_unchecked(s.len()) })
+    #[cfg(feature = "profiling")]
+    #[test]
+    fn profiled() {
+        let input: Vec<String> = (0..VEC_LEN).map(|i| format!("{COMMON}{i}")).collect();
+        let (prefix, stats) = input.common_prefix_profiled();
+        assert_eq!(prefix, input.common_prefix_ref());
+        // Other tests may be running concurrently, so only lower bounds are reliable.
+        assert!(stats.common_calls >= VEC_LEN as u64 - 1);
+        assert!(stats.byte_comparisons > 0);
+        #[cfg(feature = "simd")]
+        assert!(stats.chunk_comparisons >= VEC_LEN as u64 - 1);
     }
 
-    /// Returns a reference to the element which has the longest common
-    /// prefix of all data in the collection.
-    ///
-    /// Returns `None` when there is no common prefix.
-    fn common_prefix_raw_ref(&self) -> Option<&[T]>;
+    #[test]
+    fn prefix_and_suffix_pair() {
+        assert_eq!(
+            common_prefix_and_suffix_pair("prefix_a_suffix", "prefix_bb_suffix"),
+            (Some("prefix_"), Some("_suffix"))
+        );
+        assert_eq!(common_prefix_and_suffix_pair("ab", "ba"), (None, None));
+        assert_eq!(
+            common_prefix_and_suffix_pair("aa", "aaa"),
+            (Some("aa"), Some("aa"))
+        );
+        assert_eq!(common_prefix_and_suffix_pair("", "abc"), (None, None));
 
-    /// Returns a reference to the element which has the longest common
-    /// suffix of all data in the collection.
-    ///
-    /// Returns `None` when there is no common suffix.
-    fn common_suffix_raw_ref(&self) -> Option<&[T]>;
-}
+        let long_a = format!("{COMMON}{COMMON}_a_{COMMON}");
+        let long_b = format!("{COMMON}{COMMON}_b_{COMMON}");
+        let (prefix, suffix) = common_prefix_and_suffix_pair(&long_a, &long_b);
+        assert_eq!(prefix.unwrap(), format!("{COMMON}{COMMON}_"));
+        assert_eq!(suffix.unwrap(), format!("_{COMMON}"));
+    }
 
-impl<C: ?Sized, T> CommonStr for C
-where
-    for<'a> &'a C: IntoParallelIterator<Item = &'a T>,
-    T: AsRef<str> + Sync,
-{
-    fn common_prefix_ref(&self) -> Option<&str> {
-        find_common::<_, StringPrefix, _, _>(self)
+    #[test]
+    fn homogeneity() {
+        let templated: Vec<String> = (0..100).map(|i| format!("{COMMON}{i}")).collect();
+        let loose: Vec<String> = (0..100).map(|i| format!("thi{i}")).collect();
+        assert_eq!(compare_homogeneity(&templated, &loose), Ordering::Greater);
+        assert_eq!(compare_homogeneity(&loose, &templated), Ordering::Less);
+        assert_eq!(compare_homogeneity(&loose, &loose), Ordering::Equal);
+
+        // Equally long prefixes fall back to the amount of strings.
+        let fewer = &loose[..10];
+        assert_eq!(compare_homogeneity(fewer, &loose), Ordering::Less);
+        let disjoint = ["abc", "xyz"].map(String::from);
+        let empty: Vec<String> = Vec::new();
+        assert_eq!(compare_homogeneity(&disjoint, &empty), Ordering::Greater);
     }
 
-    fn common_suffix_ref(&self) -> Option<&str> {
-        find_common::<_, StringSuffix, _, _>(self)
+    #[test]
+    fn ratio() {
+        let input = ["abcd", "abcdefgh", "abxx"];
+        assert_eq!(input.common_prefix_ratio(), Some(0.5));
+        assert_eq!(input.common_suffix_ratio(), Some(0.0));
+        let input = ["abc", "abcabc", "abcxyz"];
+        assert_eq!(input.common_prefix_ratio(), Some(1.0));
+        let input = ["abc", "abcabc", "xyzabc"];
+        assert_eq!(input.common_suffix_ratio(), Some(1.0));
+        let input = ["single"];
+        assert_eq!(input.common_prefix_ratio(), Some(1.0));
+        let input = ["", "abc"];
+        assert_eq!(input.common_prefix_ratio(), Some(0.0));
+        assert_eq!([""].common_suffix_ratio(), Some(0.0));
+        let input: [&str; 0] = [];
+        assert_eq!(input.common_prefix_ratio(), None);
+        assert_eq!(input.common_suffix_ratio(), None);
+
+        let input: Vec<String> = (0..VEC_LEN).map(|i| format!("{COMMON}{i:08}")).collect();
+        // Every index fits in 5 digits, so 3 leading zeroes are shared too.
+        let expected = (COMMON.len() + 3) as f64 / (COMMON.len() + 8) as f64;
+        assert_eq!(input.common_prefix_ratio(), Some(expected));
     }
-}
 
-impl<C: ?Sized, T, U> CommonRaw<U> for C
-where
-    for<'a> &'a C: IntoParallelIterator<Item = &'a T>,
-    T: AsRef<[U]> + Sync,
-    U: Clone + Eq + Sync,
-{
-    fn common_prefix_raw_ref(&self) -> Option<&[U]> {
-        find_common::<_, GenericPrefix, _, _>(self)
+    #[test]
+    fn adjacent() {
+        let input = ["apple", "applet", "apply", "banana", "band"].map(String::from);
+        let expected = [Some("apple"), Some("appl"), None, Some("ban")];
+        assert_eq!(input.adjacent_common_prefixes(), expected);
+
+        let input = [String::from("single")];
+        assert!(input.adjacent_common_prefixes().is_empty());
+        let input: Vec<String> = Vec::new();
+        assert!(input.adjacent_common_prefixes().is_empty());
     }
 
-    fn common_suffix_raw_ref(&self) -> Option<&[U]> {
-        find_common::<_, GenericSuffix, _, _>(self)
+    #[test]
+    fn savings() {
+        let input: Vec<String> = (0..VEC_LEN).map(|i| format!("{COMMON}{i}!")).collect();
+        let prefix_len = input.common_prefix_len().unwrap().get();
+        assert_eq!(input.prefix_savings_bytes(), prefix_len * (VEC_LEN - 1));
+        assert_eq!(input.suffix_savings_bytes(), VEC_LEN - 1);
+
+        let input = [String::from("lonely")];
+        assert_eq!(input.prefix_savings_bytes(), 0);
+        let input = ["abc", "xyz"].map(String::from);
+        assert_eq!(input.prefix_savings_bytes(), 0);
+        assert_eq!(input.suffix_savings_bytes(), 0);
+        let input: Vec<String> = Vec::new();
+        assert_eq!(input.prefix_savings_bytes(), 0);
     }
-}
 
-/// Core function for finding LCP or LCS. It looks a bit involved,
-/// but most of what goes on in here is just to ensure we satisfy the
-/// type constraints laid out by rayon.
-///
-/// The core idea is to, for each pair of referenced values, compute the
-/// result of [`Finder::common`] and pass it along to be one of
-/// the values in the next pair. At any point, that result might be `None`,
-/// (there was no common prefix/suffix), causing the routine to terminate
-/// as soon as rayon is able to halt execution.
-fn find_common<C: ?Sized, F, T, U>(collection: &C) -> Option<&U>
-where
-    for<'a> &'a C: IntoParallelIterator<Item = &'a T>,
-    F: Finder<U>,
-    T: AsRef<U> + Sync,
-    U: ?Sized + Sync,
-{
-    // We need to use the `try_*` variants of fold/reduce so we can fail
-    // early when any two items don't have a common prefix/suffix.
-    collection
-        .into_par_iter()
-        .try_fold(
-            || None,
-            |previous, current| {
-                let cur_ref = current.as_ref();
-                match previous {
-                    Some(prev) => F::common(prev, cur_ref).map(Some),
-                    None => Some(Some(cur_ref)),
-                }
-            },
-        )
-        .try_reduce(
-            || None,
-            |a, b| match (a, b) {
-                (Some(a), Some(b)) => F::common(a, b).map(Some),
-                (Some(common), None) | (None, Some(common)) => Some(Some(common)),
-                (None, None) => None,
-            },
-        )
-        .flatten()
-}
+    #[test]
+    fn grouped_by() {
+        let files = [
+            "report_2023.pdf",
+            "report_2024.pdf",
+            "notes.txt",
+            "data_a.csv",
+            "data_b.csv",
+            "x.png",
+            "y.png",
+        ]
+        .map(String::from);
+        let extension = |s: &str| s.rsplit_once('.').map(|(_, ext)| ext.to_string());
+        let groups = files.common_prefix_grouped_by(extension);
+        assert_eq!(groups.len(), 3);
+        assert_eq!(groups[&Some("pdf".to_string())], "report_202");
+        assert_eq!(groups[&Some("txt".to_string())], "notes.txt");
+        assert_eq!(groups[&Some("csv".to_string())], "data_");
 
-#[cfg(test)]
-mod tests {
-    use super::{CommonRaw, CommonStr};
-    use std::hint::black_box;
-    use std::iter;
-    use ya_rand::*;
+        let input: Vec<String> = Vec::new();
+        assert!(input.common_prefix_grouped_by(str::len).is_empty());
+    }
 
-    const BASE_LEN: usize = 19;
-    const COMMON: &str = "this is just a simple sentence";
-    const EXT_LEN: usize = 13;
-    const TOTAL_LEN: usize = BASE_LEN + EXT_LEN;
-    const VEC_LEN: usize = 1 << 15;
+    #[test]
+    fn suffix_contained() {
+        // Long enough that the chunked comparison gets involved, and the
+        // longer string's chunks don't line up with the shorter one's.
+        let short = format!("{COMMON}ing");
+        let long = format!("logg{short}");
+        for input in [[short.clone(), long.clone()], [long.clone(), short.clone()]] {
+            assert_eq!(input.common_suffix_ref().unwrap(), short);
+            assert_eq!(input.common_suffix_raw_ref().unwrap(), short.as_bytes());
+            assert_eq!(
+                input.common_suffix_bytes_unchecked().unwrap(),
+                short.as_bytes()
+            );
+        }
+        for input in [["ing", "logging"], ["logging", "ing"]] {
+            let input = input.map(String::from);
+            assert_eq!(input.common_suffix_ref().unwrap(), "ing");
+            assert_eq!(input.common_suffix_raw_ref().unwrap(), b"ing");
+        }
+    }
 
     #[test]
-    fn str_prefix_sanity() {
-        let v = black_box(vec![COMMON.to_string(); BASE_LEN]);
-        let common = v.common_prefix_ref().unwrap();
-        assert_eq!(common, COMMON);
-        assert_eq!(common.len(), COMMON.len());
+    fn bridge() {
+        let lazy = || (0..VEC_LEN).map(|i| format!("{COMMON}{i}{COMMON}"));
+        let prefix = common_prefix_bridge(lazy()).unwrap();
+        assert!(prefix.starts_with(COMMON));
+        assert_eq!(prefix, lazy().collect::<Vec<_>>().common_prefix().unwrap());
+        let suffix = common_suffix_bridge(lazy()).unwrap();
+        assert!(suffix.ends_with(COMMON));
+        assert_eq!(suffix, lazy().collect::<Vec<_>>().common_suffix().unwrap());
+
+        assert!(common_prefix_bridge(["abc", "xyz"].into_iter()).is_none());
+        assert!(common_suffix_bridge(iter::empty::<String>()).is_none());
+        assert_eq!(common_suffix_bridge(iter::once("one")).unwrap(), "one");
+        assert!(common_prefix_bridge(iter::once("")).is_none());
     }
 
     #[test]
-    fn str_suffix_sanity() {
-        let v = black_box(vec![COMMON.to_string(); BASE_LEN]);
-        let common = v.common_suffix_ref().unwrap();
-        assert_eq!(common, COMMON);
-        assert_eq!(common.len(), COMMON.len());
+    fn raw_opt() {
+        let input = vec![vec![1, 2, 3], vec![1, 2, 4]];
+        assert_eq!(input.common_prefix_raw_opt(), Some(Some(&[1, 2][..])));
+        assert_eq!(input.common_suffix_raw_opt(), Some(None));
+
+        let input = vec![vec![3], Vec::new()];
+        assert_eq!(input.common_prefix_raw_opt(), Some(None));
+        assert_eq!(input.common_suffix_raw_opt(), Some(None));
+
+        let input: Vec<Vec<u8>> = Vec::new();
+        assert_eq!(input.common_prefix_raw_opt(), None);
+        assert_eq!(input.common_suffix_raw_opt(), None);
     }
 
     #[test]
-    fn raw_prefix_sanity() {
-        let v = black_box(vec![COMMON.as_bytes().to_vec(); BASE_LEN]);
-        let common = v.common_prefix_raw_ref().unwrap();
-        assert_eq!(common, COMMON.as_bytes());
-        assert_eq!(common.len(), COMMON.len());
+    fn prefix_from() {
+        let input = ["MAGIC123common_a", "MAGIC456common_b"].map(String::from);
+        assert_eq!(input.common_prefix_from(8).unwrap(), "common_");
+        assert_eq!(input.common_prefix_raw_from(8).unwrap(), b"common_");
+        assert_eq!(input.common_prefix_from(0), input.common_prefix_ref());
+        assert!(input.common_prefix_from(5).is_none());
+        assert!(input.common_prefix_from(16).is_none());
+        assert!(input.common_prefix_raw_from(100).is_none());
+
+        // Slicing in the middle of a char is treated like running out of string.
+        let input = ["ü_common", "ü_common"].map(String::from);
+        assert_eq!(input.common_prefix_from(2).unwrap(), "_common");
+        assert!(input.common_prefix_from(1).is_none());
+        assert_eq!(
+            input.common_prefix_raw_from(1).unwrap(),
+            &b"\xbc_common"[..]
+        );
+
+        let input = [String::from("short")];
+        assert!(input.common_prefix_from(5).is_none());
     }
 
     #[test]
-    fn raw_suffix_sanity() {
-        let v = black_box(vec![COMMON.as_bytes().to_vec(); BASE_LEN]);
-        let common = v.common_suffix_raw_ref().unwrap();
-        assert_eq!(common, COMMON.as_bytes());
-        assert_eq!(common.len(), COMMON.len());
+    fn raw_hex() {
+        let input = vec![
+            vec![0x0a, 0xff, 0x1c, 0x00],
+            vec![0x0a, 0xff, 0x1c, 0x01, 0x00],
+        ];
+        assert_eq!(input.common_prefix_raw_hex().unwrap(), "0a ff 1c");
+        assert_eq!(input.common_suffix_raw_hex().unwrap(), "00");
+        let input = vec![vec![1u8], vec![2u8]];
+        assert!(input.common_prefix_raw_hex().is_none());
     }
 
     #[test]
-    fn misc() {
-        let input: [String; 0] = [];
-        let prefix = input.common_prefix();
-        assert_eq!(prefix, None);
-        let suffix = input.common_suffix();
-        assert_eq!(suffix, None);
+    fn inline_storage() {
+        let words = ["inline_one", "inline_two", "inline_three"];
+        let input: Vec<SmallVec<[u8; 16]>> = words.iter().map(|w| w.bytes().collect()).collect();
+        assert_eq!(input.common_prefix_raw().unwrap(), b"inline_");
+        assert!(input.common_suffix_raw().is_none());
 
-        let input = ["just a single entry"];
-        let prefix = input.common_prefix().unwrap();
-        assert_eq!(prefix, input[0]);
-        let suffix = input.common_suffix().unwrap();
-        assert_eq!(suffix, input[0]);
+        let input: Vec<ArrayVec<u8, 16>> = words.iter().map(|w| w.bytes().collect()).collect();
+        assert_eq!(input.common_prefix_raw().unwrap(), b"inline_");
+        assert!(input.common_suffix_raw().is_none());
 
-        let input = ["foobar", "fooqux", "foodle", "fookys"];
-        let prefix = input.common_prefix().unwrap();
-        assert_eq!(prefix, "foo");
-        let suffix = input.common_suffix();
-        assert_eq!(suffix, None);
+        // Spilled to the heap shouldn't make a difference.
+        let input: Vec<SmallVec<[u8; 4]>> = words.iter().map(|w| w.bytes().collect()).collect();
+        assert_eq!(
+            input.common_prefix_raw_len().unwrap().get(),
+            "inline_".len()
+        );
+    }
 
-        let input = ["café", "caféine"];
-        let prefix = input.common_prefix().unwrap();
-        assert_eq!(prefix, "café");
-        let suffix = input.common_suffix();
-        assert_eq!(suffix, None);
+    #[test]
+    fn outliers() {
+        let mut input = vec![COMMON.to_string(); VEC_LEN];
+        let expected = [3, 1000, VEC_LEN - 1];
+        for i in expected {
+            input[i] = format!("!{COMMON}!");
+        }
+        assert_eq!(input.prefix_outliers(COMMON), expected);
+        assert_eq!(input.suffix_outliers(COMMON), expected);
+        assert!(input.prefix_outliers("").is_empty());
+        assert_eq!(input.prefix_outliers("nope").len(), VEC_LEN);
+    }
 
-        let input = ["äbc", "âbc"];
-        let prefix = input.common_prefix();
-        assert_eq!(prefix, None);
-        let suffix = input.common_suffix().unwrap();
-        assert_eq!(suffix, "bc");
+    #[cfg(feature = "arrayvec")]
+    #[test]
+    fn inline() {
+        let input = [vec![1, 2, 3, 4], vec![1, 2, 5, 4]];
+        let prefix = input.common_prefix_raw_inline::<4>().unwrap().unwrap();
+        assert_eq!(prefix.as_slice(), [1, 2]);
+        let suffix = input.common_suffix_raw_inline::<1>().unwrap().unwrap();
+        assert_eq!(suffix.as_slice(), [4]);
+        // The prefix fits exactly, but anything smaller is too small.
+        assert!(input.common_prefix_raw_inline::<2>().unwrap().is_some());
+        assert!(input.common_prefix_raw_inline::<1>().is_err());
+        let input = [vec![1], vec![2]];
+        assert_eq!(input.common_prefix_raw_inline::<4>().unwrap(), None);
+        assert_eq!(input.common_suffix_raw_inline::<0>().unwrap(), None);
+    }
 
-        let input = ["abc€", "xyz€"];
-        let prefix = input.common_prefix();
-        assert_eq!(prefix, None);
-        let suffix = input.common_suffix().unwrap();
-        assert_eq!(suffix, "€");
+    #[cfg(feature = "regex")]
+    #[test]
+    fn regex() {
+        let input = ["price: $1.50 (each)", "price: $1.55 (each)"];
+        let prefix = input.common_prefix_regex().unwrap();
+        assert_eq!(prefix.as_str(), r"^price: \$1\.5");
+        assert!(prefix.is_match("price: $1.59"));
+        assert!(!prefix.is_match("price: $1x59"));
+        assert!(!prefix.is_match("the price: $1.59"));
+        let suffix = input.common_suffix_regex().unwrap();
+        assert!(suffix.is_match("cost (each)"));
+        assert!(!suffix.is_match("cost (each) or so"));
+        assert!(!suffix.is_match("cost [each]"));
+        let input = ["abc", "xyz"];
+        assert!(input.common_prefix_regex().is_none());
+        assert!(input.common_suffix_regex().is_none());
+    }
 
-        let input = ["abcä", "defâ"];
-        let prefix = input.common_prefix();
-        assert_eq!(prefix, None);
-        let suffix = input.common_suffix();
-        assert_eq!(suffix, None);
+    #[cfg(feature = "bumpalo")]
+    #[test]
+    fn arena() {
+        let arena = bumpalo::Bump::new();
+        let input = ["arena_one_end", "arena_two_end"].map(String::from);
+        let prefix = input.common_prefix_in(&arena).unwrap();
+        let suffix = input.common_suffix_in(&arena).unwrap();
+        assert_eq!(prefix, "arena_");
+        assert_eq!(suffix, "_end");
+        // Both results are copies living in the arena, not in the inputs.
+        assert!(
+            !input
+                .iter()
+                .any(|s| s.as_bytes().as_ptr_range().contains(&prefix.as_ptr()))
+        );
 
-        let input = ["some thingy", "nothing"];
-        let prefix = input.common_prefix();
-        assert_eq!(prefix, None);
-        let suffix = input.common_suffix();
-        assert_eq!(suffix, None);
+        let input = ["abc", "xyz"].map(String::from);
+        assert!(input.common_prefix_in(&arena).is_none());
+    }
 
-        let input = ["-lol-", "_lol_"];
-        let prefix = input.common_prefix();
-        assert_eq!(prefix, None);
-        let suffix = input.common_suffix();
+    #[test]
+    fn cancellable() {
+        let input: Vec<String> = (0..VEC_LEN).map(|i| format!("{COMMON}{i}")).collect();
+        let cancel = AtomicBool::new(false);
+        let prefix = input.common_prefix_cancellable(&cancel).unwrap();
+        assert_eq!(prefix, input.common_prefix());
+        let suffix = input.common_suffix_cancellable(&cancel).unwrap();
         assert_eq!(suffix, None);
 
-        let input = ["a🤖b", "a🤡b"];
-        let prefix = input.common_prefix().unwrap();
-        assert_eq!(prefix, "a");
-        let suffix = input.common_suffix().unwrap();
-        assert_eq!(suffix, "b");
+        let cancel = AtomicBool::new(true);
+        assert_eq!(input.common_prefix_cancellable(&cancel), Err(Cancelled));
+        assert_eq!(input.common_suffix_cancellable(&cancel), Err(Cancelled));
+        // Nothing to compare means nothing to cancel.
+        let input = [String::from("single")];
+        assert_eq!(
+            input.common_prefix_cancellable(&cancel),
+            Ok(Some("single".into()))
+        );
+    }
 
-        let input = ["résumé", "résister"];
-        let prefix = input.common_prefix().unwrap();
-        assert_eq!(prefix, "rés");
-        let suffix = input.common_suffix();
-        assert_eq!(suffix, None);
+    #[test]
+    fn ignoring_digit_runs() {
+        let input = ["img001.png", "img42.png", "img7.jpg"];
+        assert_eq!(input.common_prefix_ignoring_digit_runs().unwrap(), "img.");
+        let input = ["a12b", "a345bc"];
+        assert_eq!(input.common_prefix_ignoring_digit_runs().unwrap(), "ab");
+        // Runs of different lengths still line up what comes after them.
+        let input = ["file1_x", "file23_x", "file4567_y"];
+        assert_eq!(input.common_prefix_ignoring_digit_runs().unwrap(), "file_");
+        let input = ["v1", "v23"];
+        assert_eq!(input.common_prefix_ignoring_digit_runs().unwrap(), "v");
+        // A digit run doesn't match the end of a string or a non-digit.
+        let input = ["a1b", "a"];
+        assert_eq!(input.common_prefix_ignoring_digit_runs().unwrap(), "a");
+        let input = ["a1b", "ab"];
+        assert_eq!(input.common_prefix_ignoring_digit_runs().unwrap(), "a");
+        let input = ["1é2ü", "33é4ö"];
+        assert_eq!(input.common_prefix_ignoring_digit_runs().unwrap(), "é");
+        let input = ["123", "45"];
+        assert_eq!(input.common_prefix_ignoring_digit_runs(), None);
+        let input = ["x1", "y1"];
+        assert_eq!(input.common_prefix_ignoring_digit_runs(), None);
+        let input: [&str; 0] = [];
+        assert_eq!(input.common_prefix_ignoring_digit_runs(), None);
 
-        let input = ["abcédef", "xyzèdef"];
-        let prefix = input.common_prefix();
-        assert_eq!(prefix, None);
-        let suffix = input.common_suffix().unwrap();
-        assert_eq!(suffix, "def");
+        let input: Vec<String> = (0..VEC_LEN).map(|i| format!("{i}{COMMON}{i}!")).collect();
+        let expected = format!("{COMMON}!");
+        assert_eq!(input.common_prefix_ignoring_digit_runs().unwrap(), expected);
+    }
 
-        let input = ["Goodbye 👋", "Farewell 👋"];
-        let prefix = input.common_prefix();
-        assert_eq!(prefix, None);
-        let suffix = input.common_suffix().unwrap();
-        assert_eq!(suffix, " 👋");
+    #[test]
+    fn typed() {
+        let input = ["typed_one", "typed_two", "typed_three"].map(String::from);
+        let prefix = input.common_prefix_typed().unwrap();
+        assert_eq!(prefix.as_str(), "typed_");
+        assert_eq!(prefix.len().get(), "typed_".len());
+        assert_eq!(prefix.to_string(), "typed_");
+        assert!(input[prefix.source_index()].starts_with(prefix.as_str()));
+        assert_eq!(
+            input[prefix.source_index()].as_ptr(),
+            prefix.as_str().as_ptr()
+        );
 
-        let input = ["Family: 👨‍👩‍👧", "Group: 👨‍👩‍👧"];
-        let prefix = input.common_prefix();
-        assert_eq!(prefix, None);
-        let suffix = input.common_suffix().unwrap();
-        assert_eq!(suffix, ": 👨‍👩‍👧");
+        let input = ["abc", "xyz"].map(String::from);
+        assert!(input.common_prefix_typed().is_none());
+        assert!([""].common_prefix_typed().is_none());
+        let prefix = ["single"].common_prefix_typed().unwrap();
+        assert_eq!((prefix.as_str(), prefix.len().get()), ("single", 6));
+        assert_eq!(prefix.source_index(), 0);
+    }
 
-        let input = ["just some words 世界", "世界"];
-        let prefix = input.common_prefix();
-        assert_eq!(prefix, None);
-        let suffix = input.common_suffix().unwrap();
-        assert_eq!(suffix, "世界");
+    #[test]
+    fn len_or_zero() {
+        let input = ["prefix_one_suffix", "prefix_two_suffix"].map(String::from);
+        assert_eq!(input.common_prefix_len_or_zero(), "prefix_".len());
+        assert_eq!(input.common_suffix_len_or_zero(), "_suffix".len());
+        assert_eq!(input.common_prefix_raw_len_or_zero(), "prefix_".len());
+        assert_eq!(input.common_suffix_raw_len_or_zero(), "_suffix".len());
 
-        let input = ["tests😀", "best😀"];
-        let prefix = input.common_prefix();
-        assert_eq!(prefix, None);
-        let suffix = input.common_suffix().unwrap();
-        assert_eq!(suffix, "😀");
+        let input = ["abc", "xyz"].map(String::from);
+        assert_eq!(input.common_prefix_len_or_zero(), 0);
+        assert_eq!(input.common_suffix_len_or_zero(), 0);
+        assert_eq!(input.common_prefix_raw_len_or_zero(), 0);
+        assert_eq!(input.common_suffix_raw_len_or_zero(), 0);
+    }
 
-        let input = ["wowie_bruhther_clap", "wowie-lol-clap", "wowie_xd_clap"];
-        let prefix = input.common_prefix().unwrap();
-        assert_eq!(prefix, "wowie");
-        let suffix = input.common_suffix().unwrap();
-        assert_eq!(suffix, "clap");
+    #[test]
+    fn raw_while() {
+        let same_parity = |a: &u32, b: &u32| a % 2 == b % 2;
+        let input = vec![
+            vec![1, 2, 3, 4, 5],
+            vec![3, 4, 5, 7, 9],
+            vec![7, 0, 9, 6, 3],
+        ];
+        assert_eq!(
+            input.common_prefix_raw_while(same_parity).unwrap(),
+            [1, 2, 3]
+        );
+        assert_eq!(input.common_suffix_raw_while(same_parity).unwrap(), [5]);
+        assert!(input.common_prefix_raw_while(|_, _| false).is_none());
+
+        // Equality should always agree with the regular methods.
+        let input = vec![b"abcdef".to_vec(), b"abcxef".to_vec(), b"abcdzef".to_vec()];
+        assert_eq!(
+            input.common_prefix_raw_while(u8::eq),
+            input.common_prefix_raw_ref()
+        );
+        assert_eq!(
+            input.common_suffix_raw_while(u8::eq),
+            input.common_suffix_raw_ref()
+        );
     }
 
     #[test]
@@ -465,4 +3486,91 @@ mod tests {
     {
         iter::repeat_with(f).take(SIZE).collect()
     }
+
+    #[test]
+    fn sets() {
+        const ROUNDS: usize = 8;
+        const SET_LEN: usize = 1 << 12;
+        let mut rng = new_rng();
+        let base = new_string_with::<BASE_LEN, _>(|| random_char(&mut rng));
+        let mut strings: Vec<String> = (0..SET_LEN)
+            .map(|_| {
+                let ext = new_string_with::<EXT_LEN, _>(|| random_char(&mut rng));
+                format!("{base}{ext}{base}")
+            })
+            .collect();
+        for _ in 0..ROUNDS {
+            shuffle(&mut rng, &mut strings);
+            let hash_set: HashSet<_> = strings.iter().cloned().collect();
+            assert_eq!(hash_set.common_prefix().unwrap(), base);
+            assert_eq!(hash_set.common_suffix().unwrap(), base);
+            let btree_set: BTreeSet<_> = strings.iter().cloned().collect();
+            assert_eq!(btree_set.common_prefix().unwrap(), base);
+            assert_eq!(btree_set.common_suffix().unwrap(), base);
+        }
+    }
+
+    #[test]
+    fn in_pool() {
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(2)
+            .build()
+            .unwrap();
+        let input = vec![COMMON.to_string(); VEC_LEN];
+        assert_eq!(input.common_prefix_in_pool(&pool).unwrap(), COMMON);
+        assert_eq!(input.common_suffix_in_pool(&pool).unwrap(), COMMON);
+        let input = vec![COMMON.as_bytes().to_vec(); VEC_LEN];
+        assert_eq!(
+            input.common_prefix_raw_in_pool(&pool).unwrap(),
+            COMMON.as_bytes()
+        );
+        assert_eq!(
+            input.common_suffix_raw_in_pool(&pool).unwrap(),
+            COMMON.as_bytes()
+        );
+        // Nested installs from within another pool still work.
+        let outer = rayon::ThreadPoolBuilder::new()
+            .num_threads(1)
+            .build()
+            .unwrap();
+        let nested = outer.install(|| input.common_prefix_raw_in_pool(&pool));
+        assert_eq!(nested.unwrap(), COMMON.as_bytes());
+    }
+
+    fn shuffle<T>(rng: &mut ShiroRng, slice: &mut [T]) {
+        for i in (1..slice.len()).rev() {
+            let j = (rng.u64() % (i as u64 + 1)) as usize;
+            slice.swap(i, j);
+        }
+    }
+
+    /// Borrows as a long string for its first few calls to `as_ref`, and as a
+    /// single char after that, which safe `AsRef` implementations are free to do.
+    struct Shrinking(AtomicUsize);
+
+    impl Shrinking {
+        fn new(long_calls: usize) -> Self {
+            Self(AtomicUsize::new(long_calls))
+        }
+    }
+
+    impl AsRef<str> for Shrinking {
+        fn as_ref(&self) -> &str {
+            let long = self
+                .0
+                .fetch_update(AtomicOrdering::Relaxed, AtomicOrdering::Relaxed, |n| {
+                    n.checked_sub(1)
+                });
+            match long {
+                Ok(_) => "abcdefgh",
+                Err(_) => "a",
+            }
+        }
+    }
+
+    impl AsRef<[u8]> for Shrinking {
+        fn as_ref(&self) -> &[u8] {
+            AsRef::<str>::as_ref(self).as_bytes()
+        }
+    }
 }
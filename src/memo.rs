@@ -0,0 +1,208 @@
+/*!
+Contains [`Memoized`], which remembers the last common prefix/suffix it found and returns
+it again for collections that look the same, without comparing any strings.
+
+Unlike [`Prepared`](crate::Prepared), which owns its collection and knows exactly when it
+changes, [`Memoized`] only ever sees borrowed collections, which may be different values
+every time. So it has to recognize a collection it has already seen by a fingerprint,
+and the fingerprint is what decides whether a cached result is correct.
+
+Results are cached as owned `String`s, since a borrow from a previous collection
+can't outlive it.
+*/
+
+use crate::finder::*;
+use crate::reduce_common;
+use rayon::prelude::*;
+use std::hash::{DefaultHasher, Hash, Hasher};
+
+/// How [`Memoized`] tells whether a collection is the same one it saw last time.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Fingerprint {
+    /// Hashes the length of the collection along with its first and last string.
+    ///
+    /// **This is a heuristic:** a collection which only differs from the previous one
+    /// somewhere in the middle gets the previous result, even when that result is wrong
+    /// for it. Only use this when collections are known to either be unchanged, or
+    /// change at their ends (like logs which get appended to).
+    #[default]
+    Sampled,
+    /// Hashes every string of the collection, ignoring their order.
+    ///
+    /// This reads every byte of every string, while a common prefix/suffix search stops
+    /// at the first mismatch, so it's never cheaper than recomputing the result. All it
+    /// saves is allocating a new `String` for a collection that hasn't changed.
+    Strict,
+}
+
+/// Cache of the most recent common prefix and suffix, keyed by a [`Fingerprint`]
+/// of the collection they were found in.
+///
+/// ```
+/// use fastxfix::{Fingerprint, Memoized};
+///
+/// let mut memo = Memoized::new(Fingerprint::Strict);
+/// let v = ["memo_a", "memo_b"];
+/// assert_eq!(memo.common_prefix(&v).unwrap(), "memo_");
+/// // Same strings in a different collection, so nothing gets compared.
+/// assert_eq!(memo.common_prefix(&v.to_vec()).unwrap(), "memo_");
+/// assert_eq!(memo.common_prefix(&["memo_a", "mem"]).unwrap(), "mem");
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct Memoized {
+    fingerprint: Fingerprint,
+    prefix: Option<Entry>,
+    suffix: Option<Entry>,
+}
+
+/// A cached result, along with the fingerprint of the collection it was found in.
+#[derive(Clone, Debug)]
+struct Entry {
+    fingerprint: u64,
+    result: Option<String>,
+}
+
+impl Memoized {
+    /// Creates an empty cache, recognizing collections by `fingerprint`.
+    pub const fn new(fingerprint: Fingerprint) -> Self {
+        Self {
+            fingerprint,
+            prefix: None,
+            suffix: None,
+        }
+    }
+
+    /// Returns the longest common prefix of all strings in `collection`, reusing the
+    /// previous result when `collection` has the same fingerprint as the last one.
+    ///
+    /// Returns `None` when there is no common prefix.
+    pub fn common_prefix<C: ?Sized, T>(&mut self, collection: &C) -> Option<&str>
+    where
+        for<'a> &'a C: IntoParallelIterator<Item = &'a T>,
+        T: AsRef<str> + Sync,
+    {
+        let fingerprint = self.fingerprint(collection);
+        lookup(&mut self.prefix, fingerprint, || {
+            let iter = collection.into_par_iter().map(|s| s.as_ref());
+            reduce_common::<StringPrefix, _, _>(iter).map(str::to_string)
+        })
+    }
+
+    /// Returns the longest common suffix of all strings in `collection`, reusing the
+    /// previous result when `collection` has the same fingerprint as the last one.
+    ///
+    /// Returns `None` when there is no common suffix.
+    pub fn common_suffix<C: ?Sized, T>(&mut self, collection: &C) -> Option<&str>
+    where
+        for<'a> &'a C: IntoParallelIterator<Item = &'a T>,
+        T: AsRef<str> + Sync,
+    {
+        let fingerprint = self.fingerprint(collection);
+        lookup(&mut self.suffix, fingerprint, || {
+            let iter = collection.into_par_iter().map(|s| s.as_ref());
+            reduce_common::<StringSuffix, _, _>(iter).map(str::to_string)
+        })
+    }
+
+    /// Discards any cached results, forcing the next queries to be recomputed.
+    pub fn clear(&mut self) {
+        self.prefix = None;
+        self.suffix = None;
+    }
+
+    fn fingerprint<C: ?Sized, T>(&self, collection: &C) -> u64
+    where
+        for<'a> &'a C: IntoParallelIterator<Item = &'a T>,
+        T: AsRef<str> + Sync,
+    {
+        let iter = || collection.into_par_iter().map(|s| s.as_ref());
+        let len = iter().opt_len().unwrap_or_else(|| iter().count());
+        match self.fingerprint {
+            Fingerprint::Sampled => {
+                let first = iter().find_first(|_| true);
+                let last = iter().find_last(|_| true);
+                hash_of((len, first, last))
+            }
+            Fingerprint::Strict => {
+                // A common prefix/suffix doesn't depend on the order of the strings,
+                // so neither does the fingerprint.
+                let sum = iter().map(hash_of).reduce(|| 0, u64::wrapping_add);
+                hash_of((len, sum))
+            }
+        }
+    }
+}
+
+/// Returns the result cached in `slot` if it has the same `fingerprint`,
+/// otherwise replaces it with the result of `compute`.
+fn lookup<F>(slot: &mut Option<Entry>, fingerprint: u64, compute: F) -> Option<&str>
+where
+    F: FnOnce() -> Option<String>,
+{
+    let entry = match slot.take() {
+        Some(entry) if entry.fingerprint == fingerprint => entry,
+        _ => Entry {
+            fingerprint,
+            result: compute(),
+        },
+    };
+    slot.insert(entry).result.as_deref()
+}
+
+fn hash_of<T: Hash>(value: T) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Fingerprint, Memoized};
+    use std::collections::HashSet;
+
+    #[test]
+    fn sampled() {
+        let mut strings: Vec<String> = (0..1 << 12).map(|i| format!("memo_{i}_end")).collect();
+        let mut memo = Memoized::default();
+        assert_eq!(memo.common_prefix(&strings).unwrap(), "memo_");
+        assert_eq!(memo.common_suffix(&strings).unwrap(), "_end");
+
+        // Only the middle changed, so the stale results get returned.
+        strings[1 << 11] = String::from("other");
+        assert_eq!(memo.common_prefix(&strings).unwrap(), "memo_");
+        assert_eq!(memo.common_suffix(&strings).unwrap(), "_end");
+        memo.clear();
+        assert!(memo.common_prefix(&strings).is_none());
+
+        strings.pop();
+        strings.push(String::from("memo_x"));
+        strings[1 << 11] = String::from("memo_y_end");
+        assert_eq!(memo.common_prefix(&strings).unwrap(), "memo_");
+        assert!(memo.common_suffix(&strings).is_none());
+        let empty: [&str; 0] = [];
+        assert!(memo.common_prefix(&empty).is_none());
+    }
+
+    #[test]
+    fn strict() {
+        let mut strings: Vec<String> = (0..1 << 12).map(|i| format!("memo_{i}_end")).collect();
+        let mut memo = Memoized::new(Fingerprint::Strict);
+        assert_eq!(memo.common_prefix(&strings).unwrap(), "memo_");
+        assert_eq!(memo.common_prefix(&strings).unwrap(), "memo_");
+
+        strings[1 << 11] = String::from("other_end");
+        assert!(memo.common_prefix(&strings).is_none());
+        assert_eq!(memo.common_suffix(&strings).unwrap(), "_end");
+        // Reordering the strings doesn't change the result, so it's still cached.
+        strings.swap(0, 1 << 11);
+        assert_eq!(memo.common_suffix(&strings).unwrap(), "_end");
+        let set: HashSet<String> = strings.iter().cloned().collect();
+        assert_eq!(memo.common_suffix(&set).unwrap(), "_end");
+        strings[0] = String::from("memo_end");
+        assert_eq!(memo.common_prefix(&strings).unwrap(), "memo_");
+        // A single empty string has nothing in common, whether cached or not.
+        assert!(memo.common_prefix(&[""]).is_none());
+        assert!(memo.common_prefix(&[""]).is_none());
+        assert!(Memoized::default().common_suffix(&[""]).is_none());
+    }
+}
@@ -0,0 +1,204 @@
+/*!
+Contains the "combine" step of map-reduce style common prefix/suffix searches, where
+each shard of the data finds its own partial result, and those get merged afterwards.
+
+Finding common prefixes/suffixes is associative, so merging the results of every shard
+gives exactly what searching all the data at once would. [`merge_prefixes`] and
+[`merge_suffixes`] do this for results that are already at hand, while [`Partial`]
+keeps track of everything a shard's result can be, and can be sent between processes
+when the `serde` feature is enabled.
+*/
+
+use crate::finder::*;
+use crate::{keep_common, reduce_common};
+use rayon::prelude::*;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Merges the common prefixes found by separate shards into the common prefix of
+/// all their data, borrowed from one of the `partials`.
+///
+/// Shards which held no strings at all must be left out, since there's no prefix
+/// to represent them. A shard whose strings had nothing in common should pass an
+/// empty string, which leaves nothing in common overall.
+///
+/// Returns `None` when there is no common prefix.
+pub fn merge_prefixes<'a>(partials: &[&'a str]) -> Option<&'a str> {
+    reduce_common::<StringPrefix, _, _>(partials.par_iter().copied())
+}
+
+/// Merges the common suffixes found by separate shards into the common suffix of
+/// all their data, borrowed from one of the `partials`.
+///
+/// Shards which held no strings at all must be left out, since there's no suffix
+/// to represent them. A shard whose strings had nothing in common should pass an
+/// empty string, which leaves nothing in common overall.
+///
+/// Returns `None` when there is no common suffix.
+pub fn merge_suffixes<'a>(partials: &[&'a str]) -> Option<&'a str> {
+    reduce_common::<StringSuffix, _, _>(partials.par_iter().copied())
+}
+
+/// Common prefix/suffix of a single shard, in a form that can be merged with those
+/// of other shards. With the `serde` feature, it can be serialized so shards can ship
+/// it to whatever merges them.
+///
+/// Merging is associative and commutative, with [`Partial::Empty`] as the identity.
+/// Prefixes and suffixes must not be merged with each other.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Partial {
+    /// The shard held no strings, so it doesn't constrain the result.
+    #[default]
+    Empty,
+    /// Every string of the shard shared this prefix/suffix.
+    Common(String),
+    /// The strings of the shard had nothing in common, so nothing is shared overall.
+    Disjoint,
+}
+
+impl Partial {
+    /// Finds the common prefix of every string in `shard`.
+    pub fn prefix_of<C: ?Sized, T>(shard: &C) -> Self
+    where
+        for<'a> &'a C: IntoParallelIterator<Item = &'a T>,
+        T: AsRef<str> + Sync,
+    {
+        let iter = shard.into_par_iter().map(|s| s.as_ref());
+        Self::from_result(shard, reduce_common::<StringPrefix, _, _>(iter))
+    }
+
+    /// Finds the common suffix of every string in `shard`.
+    pub fn suffix_of<C: ?Sized, T>(shard: &C) -> Self
+    where
+        for<'a> &'a C: IntoParallelIterator<Item = &'a T>,
+        T: AsRef<str> + Sync,
+    {
+        let iter = shard.into_par_iter().map(|s| s.as_ref());
+        Self::from_result(shard, reduce_common::<StringSuffix, _, _>(iter))
+    }
+
+    fn from_result<C: ?Sized, T>(shard: &C, result: Option<&str>) -> Self
+    where
+        for<'a> &'a C: IntoParallelIterator<Item = &'a T>,
+        T: Sync,
+    {
+        match result {
+            Some(common) => Self::Common(common.to_string()),
+            // Only paying for this check when there's nothing in common.
+            None if shard.into_par_iter().any(|_| true) => Self::Disjoint,
+            None => Self::Empty,
+        }
+    }
+
+    /// Merges two partial prefixes.
+    #[must_use]
+    pub fn merge_prefix(self, other: Self) -> Self {
+        self.merge::<StringPrefix>(other)
+    }
+
+    /// Merges two partial suffixes.
+    #[must_use]
+    pub fn merge_suffix(self, other: Self) -> Self {
+        self.merge::<StringSuffix>(other)
+    }
+
+    fn merge<F: Finder<str>>(self, other: Self) -> Self {
+        match (self, other) {
+            (Self::Disjoint, _) | (_, Self::Disjoint) => Self::Disjoint,
+            (Self::Empty, partial) | (partial, Self::Empty) => partial,
+            (Self::Common(a), Self::Common(b)) => match keep_common::<F>(a, &b) {
+                Some(common) => Self::Common(common),
+                None => Self::Disjoint,
+            },
+        }
+    }
+
+    /// Returns the common prefix/suffix, or `None` when there is none.
+    pub fn into_option(self) -> Option<String> {
+        match self {
+            Self::Common(common) => Some(common),
+            Self::Empty | Self::Disjoint => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Partial, merge_prefixes, merge_suffixes};
+    use crate::CommonStr;
+
+    #[test]
+    fn merge_slices() {
+        let shards = [
+            vec!["node_a/x.log", "node_a/y.log"],
+            vec!["node_b/z.log"],
+            vec!["node_c/w.log", "node_c/v.log"],
+        ];
+        let prefixes: Vec<&str> = shards
+            .iter()
+            .map(|s| s.common_prefix_ref().unwrap())
+            .collect();
+        let suffixes: Vec<&str> = shards
+            .iter()
+            .map(|s| s.common_suffix_ref().unwrap())
+            .collect();
+        let all: Vec<&str> = shards.concat();
+        assert_eq!(merge_prefixes(&prefixes), all.common_prefix_ref());
+        assert_eq!(merge_suffixes(&suffixes), all.common_suffix_ref());
+        assert_eq!(merge_prefixes(&["abc", ""]), None);
+        assert_eq!(merge_prefixes(&[]), None);
+        // A single shard which had nothing in common.
+        assert_eq!(merge_prefixes(&[""]), None);
+        assert_eq!(merge_suffixes(&[""]), None);
+    }
+
+    #[test]
+    fn partials() {
+        let shards: [&[&str]; 4] = [&["ab1", "ab2"], &[], &["abc"], &["abd", "abe"]];
+        let merged = shards
+            .iter()
+            .map(|shard| Partial::prefix_of(*shard))
+            .fold(Partial::Empty, Partial::merge_prefix);
+        assert_eq!(merged, Partial::Common("ab".into()));
+        // Merging in a different order gives the same result.
+        let merged = shards
+            .iter()
+            .rev()
+            .map(|shard| Partial::prefix_of(*shard))
+            .reduce(Partial::merge_prefix)
+            .unwrap();
+        assert_eq!(merged.into_option().unwrap(), "ab");
+
+        let empty: [&str; 0] = [];
+        assert_eq!(Partial::prefix_of(&empty), Partial::Empty);
+        assert_eq!(Partial::suffix_of(&["x", "y"]), Partial::Disjoint);
+        assert_eq!(
+            Partial::suffix_of(&["x_end", "y_end"]),
+            Partial::Common("_end".into())
+        );
+        let disjoint = Partial::Common("a".into()).merge_prefix(Partial::Common("b".into()));
+        assert_eq!(disjoint, Partial::Disjoint);
+        assert_eq!(
+            disjoint.merge_prefix(Partial::Common("a".into())),
+            Partial::Disjoint
+        );
+        assert_eq!(
+            Partial::Empty.merge_suffix(Partial::Empty).into_option(),
+            None
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde() {
+        for partial in [
+            Partial::Empty,
+            Partial::Common("shared/".into()),
+            Partial::Disjoint,
+        ] {
+            let json = serde_json::to_string(&partial).unwrap();
+            assert_eq!(serde_json::from_str::<Partial>(&json).unwrap(), partial);
+        }
+    }
+}
@@ -0,0 +1,95 @@
+/*!
+Contains the [`CommonStrNested`] trait, which allows finding the longest common prefix/suffix
+of every string in a two-level collection like `Vec<Vec<String>>`, as if it were flattened
+into a single collection.
+*/
+
+use crate::finder::*;
+use crate::reduce_common;
+use rayon::prelude::*;
+
+/// Trait for finding the longest common prefix/suffix of all strings
+/// held by a collection of 2D collections.
+///
+/// Inner collections which are empty are skipped, so they don't prevent a common
+/// prefix/suffix from being found. When every inner collection is empty (or there
+/// are none at all) there is nothing in common, and `None` is returned.
+pub trait CommonStrNested {
+    /// Returns the longest common prefix of all strings in all inner collections.
+    ///
+    /// Returns `None` when there is no common prefix.
+    fn common_prefix_nested(&self) -> Option<String> {
+        self.common_prefix_nested_ref().map(|s| s.to_string())
+    }
+
+    /// Returns the longest common suffix of all strings in all inner collections.
+    ///
+    /// Returns `None` when there is no common suffix.
+    fn common_suffix_nested(&self) -> Option<String> {
+        self.common_suffix_nested_ref().map(|s| s.to_string())
+    }
+
+    /// Returns a reference to the longest common prefix of all strings
+    /// in all inner collections.
+    ///
+    /// Returns `None` when there is no common prefix.
+    fn common_prefix_nested_ref(&self) -> Option<&str>;
+
+    /// Returns a reference to the longest common suffix of all strings
+    /// in all inner collections.
+    ///
+    /// Returns `None` when there is no common suffix.
+    fn common_suffix_nested_ref(&self) -> Option<&str>;
+}
+
+impl<C: ?Sized, I, T> CommonStrNested for C
+where
+    for<'a> &'a C: IntoParallelIterator<Item = &'a I>,
+    for<'a> &'a I: IntoParallelIterator<Item = &'a T>,
+    I: Sync + 'static,
+    T: AsRef<str> + Sync,
+{
+    fn common_prefix_nested_ref(&self) -> Option<&str> {
+        let iter = self
+            .into_par_iter()
+            .flat_map(|inner| inner.into_par_iter().map(|s| s.as_ref()));
+        reduce_common::<StringPrefix, _, _>(iter)
+    }
+
+    fn common_suffix_nested_ref(&self) -> Option<&str> {
+        let iter = self
+            .into_par_iter()
+            .flat_map(|inner| inner.into_par_iter().map(|s| s.as_ref()));
+        reduce_common::<StringSuffix, _, _>(iter)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CommonStrNested;
+    use crate::CommonStr;
+    use std::collections::HashSet;
+
+    #[test]
+    fn nested() {
+        let groups: Vec<Vec<String>> = (0..64)
+            .map(|i| (0..i).map(|j| format!("template_{i}_{j}.log")).collect())
+            .collect();
+        let flat = groups.concat();
+        assert_eq!(groups.common_prefix_nested().unwrap(), "template_");
+        assert_eq!(groups.common_suffix_nested().unwrap(), ".log");
+        assert_eq!(groups.common_prefix_nested_ref(), flat.common_prefix_ref());
+
+        let groups = vec![
+            HashSet::from([String::from("one_x"), String::from("one_y")]),
+            HashSet::from([String::from("one_z")]),
+        ];
+        assert_eq!(groups.common_prefix_nested().unwrap(), "one_");
+        assert!(groups.common_suffix_nested().is_none());
+
+        let groups: Vec<Vec<String>> = vec![Vec::new(), Vec::new()];
+        assert!(groups.common_prefix_nested().is_none());
+        let groups: Vec<Vec<String>> = Vec::new();
+        assert!(groups.common_suffix_nested().is_none());
+    }
+}
@@ -0,0 +1,312 @@
+/*!
+Contains the [`CommonNormalized`] trait, which finds common prefixes/suffixes of strings
+under Unicode canonical equivalence.
+
+Canonically equivalent strings can be encoded differently: `é` may be the single char
+U+00E9, or an `e` followed by the combining acute accent U+0301. The regular finders
+compare bytes, so they consider those different. Here, every string is split into
+segments of a starter char followed by its combining marks, and the canonical
+decompositions (NFD) of those segments are compared as one stream, so a char like the
+Hangul syllable `가` still matches the two starters it decomposes into. Results always
+end where both strings are at a segment boundary, and consist of whole segments of one
+of the original strings, so no accent ever gets cut off its base char.
+
+This is correctness over speed: segments are decomposed as they're compared, which is
+far slower than the byte-level finders, so only use it for data which actually needs it.
+Only canonical equivalence is considered. Locale-specific collation rules (and
+compatibility equivalents like `ﬁ` and `fi`) are out of scope.
+The trait also has a cheaper middle ground for text that's already normalized: byte
+comparisons like the regular finders, except that a base char is never kept without the
+combining marks which follow it in any of the strings. Visually, `e` and `é` differ,
+so a string ending in `e` doesn't share that `e` with one continuing with an accent.
+*/
+
+use crate::finder::*;
+use crate::reduce_common;
+use rayon::prelude::*;
+use unicode_normalization::UnicodeNormalization;
+use unicode_normalization::char::{canonical_combining_class, is_combining_mark};
+
+/// Trait for finding the longest common prefix/suffix, under canonical equivalence,
+/// of all strings in any 2D collection.
+pub trait CommonNormalized {
+    /// Returns a reference to the longest common prefix, under canonical
+    /// equivalence, of all strings in the collection, borrowed from one of them.
+    ///
+    /// Returns `None` when there is no common prefix.
+    fn common_prefix_normalized(&self) -> Option<&str>;
+
+    /// Returns a reference to the longest common suffix, under canonical
+    /// equivalence, of all strings in the collection, borrowed from one of them.
+    ///
+    /// Returns `None` when there is no common suffix.
+    fn common_suffix_normalized(&self) -> Option<&str>;
+
+    /// Returns a reference to the longest common prefix of all strings in the
+    /// collection, excluding a final base char that's followed by a combining mark
+    /// in any of the strings.
+    ///
+    /// Strings are compared byte by byte, without any normalization. When the prefix
+    /// would end right before a combining mark in one of the strings, it's shortened
+    /// until it ends before a base char instead, so `"cafe"` and `"cafe\u{301}"` only
+    /// share `"caf"`.
+    ///
+    /// Returns `None` when there is no common prefix.
+    fn common_prefix_whole_marks(&self) -> Option<&str>;
+
+    /// Returns a reference to the longest common suffix of all strings in the
+    /// collection, excluding any combining marks it would begin with.
+    ///
+    /// Strings are compared byte by byte, without any normalization. A suffix beginning
+    /// with a combining mark would cut it off its base char, so the suffix is shortened
+    /// until it begins with a base char instead.
+    ///
+    /// Returns `None` when there is no common suffix.
+    fn common_suffix_whole_marks(&self) -> Option<&str>;
+}
+
+impl<C: ?Sized, T> CommonNormalized for C
+where
+    for<'a> &'a C: IntoParallelIterator<Item = &'a T>,
+    T: AsRef<str> + Sync,
+{
+    fn common_prefix_normalized(&self) -> Option<&str> {
+        let iter = self.into_par_iter().map(|s| s.as_ref());
+        reduce_common::<NormalizedPrefix, _, _>(iter)
+    }
+
+    fn common_suffix_normalized(&self) -> Option<&str> {
+        let iter = self.into_par_iter().map(|s| s.as_ref());
+        reduce_common::<NormalizedSuffix, _, _>(iter)
+    }
+
+    fn common_prefix_whole_marks(&self) -> Option<&str> {
+        let iter = self.into_par_iter().map(|s| s.as_ref());
+        reduce_common::<WholeMarksPrefix, _, _>(iter)
+    }
+
+    fn common_suffix_whole_marks(&self) -> Option<&str> {
+        let iter = self.into_par_iter().map(|s| s.as_ref());
+        reduce_common::<WholeMarksSuffix, _, _>(iter)
+    }
+}
+
+struct NormalizedPrefix;
+impl Finder<str> for NormalizedPrefix {
+    fn common<'a>(a: &'a str, b: &str) -> Option<&'a str> {
+        let (a_segments, b_segments) = (segments(a), segments(b));
+        let end = equivalent_len(a_segments.into_iter(), b_segments.into_iter(), |s, buf| {
+            buf.extend(s.nfd())
+        });
+        (end > 0).then(|| get_unchecked!(a, ..end))
+    }
+}
+
+struct NormalizedSuffix;
+impl Finder<str> for NormalizedSuffix {
+    fn common<'a>(a: &'a str, b: &str) -> Option<&'a str> {
+        let (a_segments, b_segments) = (segments(a), segments(b));
+        let end = equivalent_len(
+            a_segments.into_iter().rev(),
+            b_segments.into_iter().rev(),
+            |s, buf| {
+                // Segments come last to first, so their decompositions are reversed too.
+                let begin = buf.len();
+                buf.extend(s.nfd());
+                buf[begin..].reverse();
+            },
+        );
+        (end > 0).then(|| get_unchecked!(a, a.len() - end..))
+    }
+}
+
+struct WholeMarksPrefix;
+impl Finder<str> for WholeMarksPrefix {
+    fn common<'a>(a: &'a str, b: &str) -> Option<&'a str> {
+        let mut end = StringPrefix::common(a, b)?.len();
+        // `end` is a char boundary of both strings, since they're equal up to it.
+        while starts_with_mark(get_unchecked!(a, end..))
+            || starts_with_mark(get_unchecked!(b, end..))
+        {
+            match get_unchecked!(a, ..end).char_indices().next_back() {
+                Some((i, _)) => end = i,
+                None => return None,
+            }
+        }
+        (end > 0).then(|| get_unchecked!(a, ..end))
+    }
+}
+
+struct WholeMarksSuffix;
+impl Finder<str> for WholeMarksSuffix {
+    fn common<'a>(a: &'a str, b: &str) -> Option<&'a str> {
+        let mut suffix = StringSuffix::common(a, b)?;
+        while starts_with_mark(suffix) {
+            let mut chars = suffix.chars();
+            chars.next();
+            suffix = chars.as_str();
+        }
+        (!suffix.is_empty()).then_some(suffix)
+    }
+}
+
+/// Returns whether the first char of `s` is a combining mark.
+fn starts_with_mark(s: &str) -> bool {
+    s.chars().next().is_some_and(is_combining_mark)
+}
+
+/// Splits `s` before every starter (char with a canonical combining class of 0),
+/// so each segment is a starter followed by the combining marks applied to it.
+/// Combining marks at the very start of `s` form a segment without a starter.
+fn segments(s: &str) -> Vec<&str> {
+    let mut segments = Vec::new();
+    let mut begin = 0;
+    for (i, c) in s.char_indices() {
+        if i > 0 && canonical_combining_class(c) == 0 {
+            segments.push(get_unchecked!(s, begin..i));
+            begin = i;
+        }
+    }
+    if begin < s.len() {
+        segments.push(get_unchecked!(s, begin..));
+    }
+    segments
+}
+
+/// Returns the length in bytes of the longest run of `a` segments whose canonical
+/// decomposition equals that of a run of `b` segments.
+///
+/// A segment can decompose into several starters (like Hangul syllables), so the
+/// segments of both sides don't match one-to-one. Instead, whichever side has decomposed
+/// fewer chars so far takes its next segment, and only the chars which are still pending
+/// on one side are kept. When neither side has any pending, both are at a segment boundary.
+fn equivalent_len<'a, 'b>(
+    mut a: impl Iterator<Item = &'a str>,
+    mut b: impl Iterator<Item = &'b str>,
+    decompose: impl Fn(&str, &mut Vec<char>),
+) -> usize {
+    let (mut a_pending, mut b_pending) = (Vec::new(), Vec::new());
+    let (mut taken, mut end) = (0, 0);
+    loop {
+        if a_pending.len() <= b_pending.len() {
+            let Some(segment) = a.next() else { break };
+            taken += segment.len();
+            decompose(segment, &mut a_pending);
+        } else {
+            let Some(segment) = b.next() else { break };
+            decompose(segment, &mut b_pending);
+        }
+        let n = a_pending.len().min(b_pending.len());
+        if a_pending[..n] != b_pending[..n] {
+            break;
+        }
+        a_pending.drain(..n);
+        b_pending.drain(..n);
+        if a_pending.is_empty() && b_pending.is_empty() {
+            end = taken;
+        }
+    }
+    end
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CommonNormalized;
+    use crate::CommonStr;
+
+    #[test]
+    fn composed_and_decomposed() {
+        let composed = "caf\u{e9}_menu";
+        let decomposed = "cafe\u{301}_menu";
+        let input = [composed, decomposed];
+        assert_eq!(input.common_prefix_ref().unwrap(), "caf");
+        assert_eq!(input.common_prefix_normalized().unwrap(), composed);
+        assert_eq!(input.common_suffix_normalized().unwrap(), composed);
+
+        let input = [decomposed, "caf\u{e9}_bar"];
+        assert_eq!(input.common_prefix_normalized().unwrap(), "cafe\u{301}_");
+        // A base char is never split from its accent.
+        let input = ["cafe\u{301}", "cafe"];
+        assert_eq!(input.common_prefix_normalized().unwrap(), "caf");
+        assert_eq!(input.common_suffix_normalized(), None);
+        let input = ["x_e\u{301}", "y_e"];
+        assert_eq!(input.common_suffix_normalized(), None);
+    }
+
+    #[test]
+    fn mark_order() {
+        // Marks with different combining classes can appear in either order.
+        let a = "q\u{323}\u{307}x";
+        let b = "q\u{307}\u{323}y";
+        let input = [a, b];
+        assert_eq!(input.common_prefix_normalized().unwrap(), "q\u{323}\u{307}");
+        assert_eq!(input.common_suffix_normalized(), None);
+        let input = ["\u{301}abc", "\u{301}abd"];
+        assert_eq!(input.common_prefix_normalized().unwrap(), "\u{301}ab");
+        let input: [&str; 0] = [];
+        assert_eq!(input.common_prefix_normalized(), None);
+    }
+
+    #[test]
+    fn several_starters() {
+        // A precomposed Hangul syllable decomposes into two starters.
+        let input = ["\u{ac00}x", "\u{1100}\u{1161}x"];
+        assert_eq!(input.common_prefix_normalized().unwrap(), "\u{ac00}x");
+        assert_eq!(input.common_suffix_normalized().unwrap(), "\u{ac00}x");
+        let input = ["\u{1100}\u{1161}y", "\u{ac00}x"];
+        assert_eq!(
+            input.common_prefix_normalized().unwrap(),
+            "\u{1100}\u{1161}"
+        );
+        let input = ["y\u{ac00}", "x\u{1100}\u{1161}"];
+        assert_eq!(input.common_suffix_normalized().unwrap(), "\u{ac00}");
+        // The syllable is never cut in the middle.
+        let input = ["\u{ac00}", "\u{1100}x"];
+        assert_eq!(input.common_prefix_normalized(), None);
+        let input = ["\u{ac00}", "x\u{1161}"];
+        assert_eq!(input.common_suffix_normalized(), None);
+    }
+
+    #[test]
+    fn whole_marks() {
+        let input = ["cafe", "cafe\u{301}"];
+        assert_eq!(input.common_prefix_ref().unwrap(), "cafe");
+        assert_eq!(input.common_prefix_whole_marks().unwrap(), "caf");
+        let input = ["cafe\u{301}\u{323}x", "cafe\u{301}\u{323}y"];
+        assert_eq!(
+            input.common_prefix_whole_marks().unwrap(),
+            "cafe\u{301}\u{323}"
+        );
+        // Marks stacked on one base are all removed along with it.
+        let input = ["nai\u{308}", "nai\u{308}\u{301}"];
+        assert_eq!(input.common_prefix_whole_marks().unwrap(), "na");
+        // Precomposed chars aren't followed by a mark, so they're kept.
+        let input = ["caf\u{e9}", "caf\u{e9}s"];
+        assert_eq!(input.common_prefix_whole_marks().unwrap(), "caf\u{e9}");
+        let input = ["e", "e\u{301}"];
+        assert_eq!(input.common_prefix_whole_marks(), None);
+
+        let input = ["xe\u{301}", "ye\u{301}"];
+        assert_eq!(input.common_suffix_whole_marks().unwrap(), "e\u{301}");
+        let input = ["xe\u{301}", "ya\u{301}"];
+        assert_eq!(input.common_suffix_ref().unwrap(), "\u{301}");
+        assert_eq!(input.common_suffix_whole_marks(), None);
+        let input: [&str; 0] = [];
+        assert_eq!(input.common_prefix_whole_marks(), None);
+    }
+
+    #[test]
+    fn many() {
+        let input: Vec<String> = (0..1 << 12)
+            .map(|i| match i % 2 {
+                0 => format!("r\u{e9}sum\u{e9}_{i}_na\u{ef}ve"),
+                _ => format!("re\u{301}sume\u{301}_{i}_nai\u{308}ve"),
+            })
+            .collect();
+        assert_eq!(
+            input.common_prefix_normalized().unwrap(),
+            "r\u{e9}sum\u{e9}_"
+        );
+        assert_eq!(input.common_suffix_normalized().unwrap(), "_na\u{ef}ve");
+    }
+}
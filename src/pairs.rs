@@ -0,0 +1,112 @@
+/*!
+Contains the [`CommonStrPairs`] trait, which allows finding the longest common prefix/suffix
+of collections whose items are 2-tuples, using only the second element of each tuple.
+
+This covers both maps like `HashMap<K, String>` (which rayon iterates as `(&K, &V)`) and
+collections of tuples like `Vec<(usize, String)>` (which rayon iterates as `&(K, V)`).
+*/
+
+use crate::finder::*;
+use crate::reduce_common;
+use rayon::prelude::*;
+
+/// Abstraction over the two shapes rayon yields 2-tuples in, granting
+/// access to the second element for the lifetime of the original tuple.
+pub trait Pair<'a> {
+    /// Type of the second element.
+    type Second: ?Sized;
+
+    /// Returns a reference to the second element.
+    fn second(self) -> &'a Self::Second;
+}
+
+impl<'a, K, V> Pair<'a> for (&'a K, &'a V) {
+    type Second = V;
+
+    #[inline]
+    fn second(self) -> &'a V {
+        self.1
+    }
+}
+
+impl<'a, K, V> Pair<'a> for &'a (K, V) {
+    type Second = V;
+
+    #[inline]
+    fn second(self) -> &'a V {
+        &self.1
+    }
+}
+
+/// Trait for finding the longest common [`String`] prefix/suffix of the second
+/// element of every 2-tuple in a collection.
+pub trait CommonStrPairs {
+    /// Returns a reference to the second element of the tuple which has the longest
+    /// common prefix of all second elements in the collection.
+    ///
+    /// Returns `None` when there is no common prefix.
+    fn common_prefix_on_second(&self) -> Option<&str>;
+
+    /// Returns a reference to the second element of the tuple which has the longest
+    /// common suffix of all second elements in the collection.
+    ///
+    /// Returns `None` when there is no common suffix.
+    fn common_suffix_on_second(&self) -> Option<&str>;
+}
+
+impl<C: ?Sized> CommonStrPairs for C
+where
+    for<'a> &'a C: IntoParallelIterator<Item: Pair<'a, Second: AsRef<str>>>,
+{
+    fn common_prefix_on_second(&self) -> Option<&str> {
+        let iter = self.into_par_iter().map(|p| p.second().as_ref());
+        reduce_common::<StringPrefix, _, _>(iter)
+    }
+
+    fn common_suffix_on_second(&self) -> Option<&str> {
+        let iter = self.into_par_iter().map(|p| p.second().as_ref());
+        reduce_common::<StringSuffix, _, _>(iter)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CommonStrPairs;
+    use std::collections::{BTreeMap, HashMap};
+
+    #[test]
+    fn maps() {
+        let map: HashMap<_, _> = [(1, "key_one_end"), (2, "key_two_end"), (3, "key_six_end")]
+            .into_iter()
+            .collect();
+        assert_eq!(map.common_prefix_on_second().unwrap(), "key_");
+        assert_eq!(map.common_suffix_on_second().unwrap(), "_end");
+
+        let map: BTreeMap<_, _> = [("a", "same".to_string()), ("b", "different".to_string())]
+            .into_iter()
+            .collect();
+        assert_eq!(map.common_prefix_on_second(), None);
+        assert_eq!(map.common_suffix_on_second(), None);
+
+        let map: HashMap<u8, String> = HashMap::new();
+        assert_eq!(map.common_prefix_on_second(), None);
+    }
+
+    #[test]
+    fn tuples() {
+        let tuples: Vec<_> = ["zipped_a", "zipped_b", "zipped_c"]
+            .into_iter()
+            .map(String::from)
+            .enumerate()
+            .collect();
+        let prefix = tuples.common_prefix_on_second().unwrap();
+        assert_eq!(prefix, "zipped_");
+        // The borrow is tied to one of the tuples in the collection.
+        assert!(tuples.iter().any(|(_, s)| s.as_ptr() == prefix.as_ptr()));
+        assert_eq!(tuples.common_suffix_on_second(), None);
+
+        let tuples = [("key", "only")];
+        assert_eq!(tuples.common_prefix_on_second().unwrap(), "only");
+        assert_eq!(tuples.common_suffix_on_second().unwrap(), "only");
+    }
+}
@@ -0,0 +1,191 @@
+/*!
+Contains [`Prepared`], which owns a collection and caches its common prefix/suffix so
+repeated queries against an unchanged collection don't have to be recomputed.
+
+Since [`Prepared`] owns the collection, the only way to mutate it is through
+[`Prepared::get_mut`] (or by taking it back out with [`Prepared::into_inner`]). Handing out
+a mutable reference always invalidates the cache. Elements whose `as_ref` depends on
+interior mutability can still change behind its back though, which can make it return a
+stale result. Cached lengths are always checked against the element they slice, and the
+result gets recomputed (without being cached) when they don't fit it anymore.
+*/
+
+use crate::finder::*;
+use crate::reduce_common;
+use rayon::prelude::*;
+use std::sync::OnceLock;
+
+/// Collection wrapper which caches the results of common prefix/suffix queries.
+///
+/// Only the length of each result is stored, so a cached query costs finding
+/// the first element of the collection and slicing it.
+#[derive(Debug, Default)]
+pub struct Prepared<C> {
+    collection: C,
+    prefix_len: OnceLock<usize>,
+    suffix_len: OnceLock<usize>,
+}
+
+impl<C, T> Prepared<C>
+where
+    for<'a> &'a C: IntoParallelIterator<Item = &'a T>,
+    T: AsRef<str> + Sync,
+{
+    /// Wraps `collection`. Nothing is computed until the first query.
+    pub fn new(collection: C) -> Self {
+        Self {
+            collection,
+            prefix_len: OnceLock::new(),
+            suffix_len: OnceLock::new(),
+        }
+    }
+
+    /// Returns a reference to the longest common prefix of all strings in the
+    /// collection, computing it only if it isn't cached yet.
+    ///
+    /// Returns `None` when there is no common prefix.
+    pub fn common_prefix_ref(&self) -> Option<&str> {
+        let len = *self
+            .prefix_len
+            .get_or_init(|| self.compute::<StringPrefix>().map_or(0, str::len));
+        let first = self.first()?;
+        if len == 0 {
+            return None;
+        }
+        first.get(..len).or_else(|| self.compute::<StringPrefix>())
+    }
+
+    /// Returns a reference to the longest common suffix of all strings in the
+    /// collection, computing it only if it isn't cached yet.
+    ///
+    /// Returns `None` when there is no common suffix.
+    pub fn common_suffix_ref(&self) -> Option<&str> {
+        let len = *self
+            .suffix_len
+            .get_or_init(|| self.compute::<StringSuffix>().map_or(0, str::len));
+        let first = self.first()?;
+        if len == 0 {
+            return None;
+        }
+        first
+            .len()
+            .checked_sub(len)
+            .and_then(|begin| first.get(begin..))
+            .or_else(|| self.compute::<StringSuffix>())
+    }
+
+    /// Returns a reference to the wrapped collection.
+    pub fn get(&self) -> &C {
+        &self.collection
+    }
+
+    /// Returns a mutable reference to the wrapped collection,
+    /// invalidating any cached results.
+    pub fn get_mut(&mut self) -> &mut C {
+        self.invalidate();
+        &mut self.collection
+    }
+
+    /// Discards any cached results, forcing the next queries to be recomputed.
+    pub fn invalidate(&mut self) {
+        self.prefix_len.take();
+        self.suffix_len.take();
+    }
+
+    /// Unwraps the collection, discarding any cached results.
+    pub fn into_inner(self) -> C {
+        self.collection
+    }
+
+    fn compute<F: Finder<str>>(&self) -> Option<&str> {
+        let iter = self.collection.into_par_iter().map(|s| s.as_ref());
+        reduce_common::<F, _, _>(iter)
+    }
+
+    /// Any element can be sliced to get a cached result, since
+    /// they all share it. The first one is just the cheapest to find.
+    fn first(&self) -> Option<&str> {
+        self.collection
+            .into_par_iter()
+            .map(|s| s.as_ref())
+            .find_first(|_| true)
+    }
+}
+
+impl<C, T> From<C> for Prepared<C>
+where
+    for<'a> &'a C: IntoParallelIterator<Item = &'a T>,
+    T: AsRef<str> + Sync,
+{
+    fn from(collection: C) -> Self {
+        Self::new(collection)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Prepared;
+    use crate::CommonStr;
+    use std::collections::HashSet;
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    #[test]
+    fn cached() {
+        let strings: Vec<String> = (0..1 << 12).map(|i| format!("cached_{i}_query")).collect();
+        let mut prepared = Prepared::new(strings);
+        for _ in 0..3 {
+            assert_eq!(prepared.common_prefix_ref().unwrap(), "cached_");
+            assert_eq!(prepared.common_suffix_ref().unwrap(), "_query");
+        }
+
+        prepared.get_mut().push("cached_xquery".to_string());
+        assert_eq!(prepared.common_prefix_ref().unwrap(), "cached_");
+        assert_eq!(prepared.common_suffix_ref().unwrap(), "query");
+        prepared.get_mut()[0] = "other".to_string();
+        assert!(prepared.common_prefix_ref().is_none());
+        assert_eq!(
+            prepared.common_prefix_ref(),
+            prepared.get().common_prefix_ref()
+        );
+
+        prepared.get_mut().clear();
+        assert!(prepared.common_prefix_ref().is_none());
+        assert!(prepared.common_suffix_ref().is_none());
+    }
+
+    #[test]
+    fn unordered() {
+        let set: HashSet<String> = ["set_é_one", "set_é_two", "set_é"].map(String::from).into();
+        let prepared = Prepared::from(set);
+        assert_eq!(prepared.common_prefix_ref().unwrap(), "set_é");
+        assert_eq!(prepared.common_prefix_ref().unwrap(), "set_é");
+        assert!(prepared.common_suffix_ref().is_none());
+        assert_eq!(prepared.into_inner().len(), 3);
+    }
+
+    #[test]
+    fn interior_mutability() {
+        static SHORT: AtomicBool = AtomicBool::new(false);
+        // Borrows as its first string until `SHORT` gets set, and its second one after.
+        struct Toggled(&'static str, &'static str);
+        impl AsRef<str> for Toggled {
+            fn as_ref(&self) -> &str {
+                match SHORT.load(Ordering::Relaxed) {
+                    false => self.0,
+                    true => self.1,
+                }
+            }
+        }
+
+        let prepared = Prepared::new([
+            Toggled("prefix_one_long_suffix", "p_s"),
+            Toggled("prefix_two_long_suffix", "p_s"),
+        ]);
+        assert_eq!(prepared.common_prefix_ref().unwrap(), "prefix_");
+        assert_eq!(prepared.common_suffix_ref().unwrap(), "_long_suffix");
+        // The cached lengths no longer fit, so the results get recomputed.
+        SHORT.store(true, Ordering::Relaxed);
+        assert_eq!(prepared.common_prefix_ref().unwrap(), "p_s");
+        assert_eq!(prepared.common_suffix_ref().unwrap(), "p_s");
+    }
+}
@@ -0,0 +1,73 @@
+/*!
+Contains [`FinderStats`] and the counters backing it, which are only compiled in when the
+`profiling` feature is enabled so regular builds don't pay for the bookkeeping.
+
+The counters are process-wide atomics, since the finders have no other way of reaching the
+caller. Stats returned by [`CommonStr::common_prefix_profiled`](crate::CommonStr::common_prefix_profiled)
+are the difference between the counters before and after the query, so any other queries
+running at the same time get included in them.
+*/
+
+use std::sync::atomic::{AtomicU64, Ordering};
+
+static CHUNK_COMPARISONS: AtomicU64 = AtomicU64::new(0);
+static BYTE_COMPARISONS: AtomicU64 = AtomicU64::new(0);
+static COMMON_CALLS: AtomicU64 = AtomicU64::new(0);
+
+/// Counts of the work done while finding a common prefix/suffix.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct FinderStats {
+    /// Amount of chunks compared at once by the `simd` string comparisons.
+    pub chunk_comparisons: u64,
+    /// Amount of single bytes compared by the string comparisons,
+    /// either after the chunks ran out or because `simd` is disabled.
+    pub byte_comparisons: u64,
+    /// Amount of times two elements (or partial results) were compared.
+    pub common_calls: u64,
+}
+
+impl FinderStats {
+    /// Returns the current value of every counter.
+    pub(crate) fn snapshot() -> Self {
+        Self {
+            chunk_comparisons: CHUNK_COMPARISONS.load(Ordering::Relaxed),
+            byte_comparisons: BYTE_COMPARISONS.load(Ordering::Relaxed),
+            common_calls: COMMON_CALLS.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Returns the work done between `earlier` and `self`.
+    pub(crate) fn since(self, earlier: Self) -> Self {
+        Self {
+            chunk_comparisons: self.chunk_comparisons - earlier.chunk_comparisons,
+            byte_comparisons: self.byte_comparisons - earlier.byte_comparisons,
+            common_calls: self.common_calls - earlier.common_calls,
+        }
+    }
+}
+
+/// Amount of comparisons needed to find `equal` consecutive equal items,
+/// out of `available` items: one extra to find the mismatch, if there is one.
+#[inline]
+fn compared(equal: usize, available: usize) -> u64 {
+    (equal + 1).min(available) as u64
+}
+
+/// Records chunk comparisons which found `equal` out of `available` chunks to be equal.
+#[cfg(any(feature = "simd", test))]
+#[inline]
+pub fn record_chunks(equal: usize, available: usize) {
+    CHUNK_COMPARISONS.fetch_add(compared(equal, available), Ordering::Relaxed);
+}
+
+/// Records byte comparisons which found `equal` out of `available` bytes to be equal.
+#[inline]
+pub fn record_bytes(equal: usize, available: usize) {
+    BYTE_COMPARISONS.fetch_add(compared(equal, available), Ordering::Relaxed);
+}
+
+/// Records a single comparison of two elements.
+#[inline]
+pub fn record_call() {
+    COMMON_CALLS.fetch_add(1, Ordering::Relaxed);
+}
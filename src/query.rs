@@ -0,0 +1,205 @@
+/*!
+Contains the [`CommonQuery`] builder, which collects the optional ways a common
+prefix/suffix can be searched for into a single entry point.
+
+The methods of [`CommonStr`](crate::CommonStr) remain the shortcut for the default
+query, which is exactly what [`CommonQuery::new`] runs when left unconfigured.
+*/
+
+use crate::finder::*;
+use crate::reduce_common;
+use rayon::prelude::*;
+
+/// Which end of the strings a [`CommonQuery`] compares.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Direction {
+    /// Look for a common prefix.
+    #[default]
+    Prefix,
+    /// Look for a common suffix.
+    Suffix,
+}
+
+/// What a [`CommonQuery`] returns for a collection holding a single string.
+///
+/// Every string trivially shares all of itself with itself, which is what the
+/// methods of [`CommonStr`](crate::CommonStr) return. But when the result is used as
+/// evidence of a pattern across strings, a single string isn't evidence of anything,
+/// so some callers would rather treat it the same as having nothing in common.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum SingleElementPolicy {
+    /// The whole string is returned, as if it was its own common prefix/suffix.
+    #[default]
+    ReturnElement,
+    /// Nothing is returned, as if the single string had nothing in common.
+    ReturnNone,
+}
+
+/// Builder for configurable common prefix/suffix queries over collections of strings.
+///
+/// ```
+/// use fastxfix::{CommonQuery, Direction};
+///
+/// let v = ["Content-Type", "content-length", "CONTENT-ENCODING"];
+/// let query = CommonQuery::new().case_insensitive(true).min_len(4);
+/// assert_eq!(query.run(&v).unwrap(), "Content-");
+/// assert_eq!(query.direction(Direction::Suffix).run(&v), None);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct CommonQuery {
+    direction: Direction,
+    case_insensitive: bool,
+    min_len: usize,
+    single_element: SingleElementPolicy,
+}
+
+impl CommonQuery {
+    /// Creates a query for the case-sensitive common prefix, with no minimum length.
+    pub const fn new() -> Self {
+        Self {
+            direction: Direction::Prefix,
+            case_insensitive: false,
+            min_len: 0,
+            single_element: SingleElementPolicy::ReturnElement,
+        }
+    }
+
+    /// Sets which end of the strings gets compared.
+    #[must_use]
+    pub const fn direction(mut self, direction: Direction) -> Self {
+        self.direction = direction;
+        self
+    }
+
+    /// Sets whether ASCII letters are compared ignoring their case. The result is
+    /// borrowed from one of the strings, so it keeps that string's casing.
+    #[must_use]
+    pub const fn case_insensitive(mut self, case_insensitive: bool) -> Self {
+        self.case_insensitive = case_insensitive;
+        self
+    }
+
+    /// Sets the minimum length in bytes a result needs to have,
+    /// with any shorter result being treated as no result at all.
+    #[must_use]
+    pub const fn min_len(mut self, min_len: usize) -> Self {
+        self.min_len = min_len;
+        self
+    }
+
+    /// Sets what gets returned for a collection holding a single string.
+    #[must_use]
+    pub const fn single_element(mut self, policy: SingleElementPolicy) -> Self {
+        self.single_element = policy;
+        self
+    }
+
+    /// Runs the query against every string in `collection`, returning a reference to
+    /// the common prefix/suffix of one of them.
+    ///
+    /// Returns `None` when there is nothing in common, when the result is shorter
+    /// than [`CommonQuery::min_len`], or when there's a single string and the
+    /// policy is [`SingleElementPolicy::ReturnNone`].
+    pub fn run_ref<'a, C: ?Sized, T>(&self, collection: &'a C) -> Option<&'a str>
+    where
+        for<'b> &'b C: IntoParallelIterator<Item = &'b T>,
+        T: AsRef<str> + Sync,
+    {
+        let iter = collection.into_par_iter().map(|s| s.as_ref());
+        let result = match (self.direction, self.case_insensitive) {
+            (Direction::Prefix, false) => reduce_common::<StringPrefix, _, _>(iter),
+            (Direction::Suffix, false) => reduce_common::<StringSuffix, _, _>(iter),
+            (Direction::Prefix, true) => reduce_common::<AsciiCasePrefix, _, _>(iter),
+            (Direction::Suffix, true) => reduce_common::<AsciiCaseSuffix, _, _>(iter),
+        }?;
+        if result.len() < self.min_len {
+            return None;
+        }
+        // Only counting the strings when there's a result to keep or discard.
+        let single = || collection.into_par_iter().take_any(2).count() == 1;
+        match self.single_element {
+            SingleElementPolicy::ReturnNone if single() => None,
+            _ => Some(result),
+        }
+    }
+
+    /// Same as [`CommonQuery::run_ref`], but returns an owned [`String`].
+    pub fn run<C: ?Sized, T>(&self, collection: &C) -> Option<String>
+    where
+        for<'b> &'b C: IntoParallelIterator<Item = &'b T>,
+        T: AsRef<str> + Sync,
+    {
+        self.run_ref(collection).map(str::to_string)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CommonQuery, Direction, SingleElementPolicy};
+    use crate::CommonStr;
+
+    #[test]
+    fn default_matches_common_str() {
+        let input = ["prefix_a_suffix", "prefix_bb_suffix", "prefix_ccc_suffix"];
+        let query = CommonQuery::new();
+        assert_eq!(query, CommonQuery::default());
+        assert_eq!(query.run(&input), input.common_prefix());
+        let query = query.direction(Direction::Suffix);
+        assert_eq!(query.run(&input), input.common_suffix());
+        let input: Vec<String> = Vec::new();
+        assert_eq!(CommonQuery::new().run(&input), None);
+    }
+
+    #[test]
+    fn case_insensitive() {
+        let input = ["HeLLo_wOrld", "hello_World", "HELLO_WORLD"];
+        let query = CommonQuery::new().case_insensitive(true);
+        assert_eq!(query.run_ref(&input).unwrap(), "HeLLo_wOrld");
+        assert_eq!(CommonQuery::new().run_ref(&input), None);
+
+        let input = ["x.TXT", "y.txt", "z.Txt"];
+        let query = query.direction(Direction::Suffix);
+        assert_eq!(query.run(&input).unwrap(), ".TXT");
+
+        // Only ASCII case is ignored, and results stay on char boundaries.
+        let input = ["Éa", "éa"];
+        assert_eq!(CommonQuery::new().case_insensitive(true).run(&input), None);
+        let input = ["aÉ", "Aé"];
+        assert_eq!(
+            CommonQuery::new()
+                .case_insensitive(true)
+                .run(&input)
+                .unwrap(),
+            "a"
+        );
+    }
+
+    #[test]
+    fn min_len() {
+        let input = ["abcdef", "abcxyz"];
+        assert_eq!(CommonQuery::new().min_len(3).run(&input).unwrap(), "abc");
+        assert_eq!(CommonQuery::new().min_len(4).run(&input), None);
+        let input = ["1_ABC", "2_abc"];
+        let query = CommonQuery::new()
+            .direction(Direction::Suffix)
+            .case_insensitive(true)
+            .min_len(4);
+        assert_eq!(query.run(&input).unwrap(), "_ABC");
+    }
+
+    #[test]
+    fn single_element() {
+        let input = ["alone"];
+        assert_eq!(CommonQuery::new().run(&input).unwrap(), "alone");
+        let query = CommonQuery::new().single_element(SingleElementPolicy::ReturnNone);
+        assert_eq!(query.run(&input), None);
+        assert_eq!(query.direction(Direction::Suffix).run(&input), None);
+        let input = ["alone", "along"];
+        assert_eq!(query.run(&input).unwrap(), "alon");
+        let input = ["same"; 3];
+        assert_eq!(query.run(&input).unwrap(), "same");
+        // Keeping a single element never keeps an empty one.
+        assert_eq!(CommonQuery::new().run(&[""]), None);
+        assert_eq!(CommonQuery::new().run_ref(&[""]), None);
+    }
+}
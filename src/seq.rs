@@ -0,0 +1,122 @@
+/*!
+Contains the [`CommonStrSeq`] trait, which finds common prefixes/suffixes on the current
+thread only, for collections whose strings can't be shared across threads.
+*/
+
+use crate::finder::*;
+
+/// Trait for finding the longest common prefix/suffix of all strings in any
+/// 2D collection, without parallelism.
+///
+/// Unlike [`CommonStr`](crate::CommonStr), this doesn't require the strings to be
+/// [`Sync`], so element types like [`Rc<str>`](std::rc::Rc) work too. Comparisons
+/// still use the same (chunked) finders, just one after another.
+pub trait CommonStrSeq {
+    /// Returns the longest common prefix of all referenced strings.
+    ///
+    /// Returns `None` when there is no common prefix.
+    fn common_prefix_seq(&self) -> Option<String> {
+        self.common_prefix_seq_ref().map(str::to_string)
+    }
+
+    /// Returns the longest common suffix of all referenced strings.
+    ///
+    /// Returns `None` when there is no common suffix.
+    fn common_suffix_seq(&self) -> Option<String> {
+        self.common_suffix_seq_ref().map(str::to_string)
+    }
+
+    /// Returns a reference to the first string in the collection, limited to
+    /// the longest common prefix of all strings in the collection.
+    ///
+    /// Returns `None` when there is no common prefix.
+    fn common_prefix_seq_ref(&self) -> Option<&str>;
+
+    /// Returns a reference to the first string in the collection, limited to
+    /// the longest common suffix of all strings in the collection.
+    ///
+    /// Returns `None` when there is no common suffix.
+    fn common_suffix_seq_ref(&self) -> Option<&str>;
+}
+
+impl<C: ?Sized, T> CommonStrSeq for C
+where
+    for<'a> &'a C: IntoIterator<Item = &'a T>,
+    T: AsRef<str>,
+{
+    fn common_prefix_seq_ref(&self) -> Option<&str> {
+        fold_common::<StringPrefix>(self.into_iter().map(|s| s.as_ref()))
+    }
+
+    fn common_suffix_seq_ref(&self) -> Option<&str> {
+        fold_common::<StringSuffix>(self.into_iter().map(|s| s.as_ref()))
+    }
+}
+
+/// Sequential counterpart to `reduce_common`, stopping at the first
+/// string which has nothing in common with the ones before it. An empty
+/// first string has nothing in common with anything, even on its own.
+fn fold_common<'a, F: Finder<str>>(mut iter: impl Iterator<Item = &'a str>) -> Option<&'a str> {
+    let first = iter.next().filter(|s| !s.is_empty())?;
+    iter.try_fold(first, F::common)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CommonStrSeq;
+    use crate::CommonStr;
+    use std::cell::RefCell;
+    use std::collections::VecDeque;
+    use std::rc::Rc;
+
+    #[test]
+    fn rc() {
+        let input: Vec<Rc<str>> = vec!["shared_a_end".into(), "shared_bb_end".into()];
+        assert_eq!(input.common_prefix_seq().unwrap(), "shared_");
+        assert_eq!(input.common_suffix_seq().unwrap(), "_end");
+        let input: Vec<Rc<str>> = vec!["abc".into(), "xyz".into()];
+        assert_eq!(input.common_prefix_seq(), None);
+        assert_eq!(input.common_suffix_seq_ref(), None);
+        let input: Vec<Rc<str>> = Vec::new();
+        assert_eq!(input.common_prefix_seq(), None);
+        let input: Vec<Rc<str>> = vec!["".into()];
+        assert_eq!(input.common_prefix_seq(), None);
+        assert_eq!(input.common_suffix_seq_ref(), None);
+    }
+
+    /// Element type which is `!Sync`, but can still be referenced as a `str`.
+    struct Tagged {
+        text: String,
+        _seen: RefCell<usize>,
+    }
+
+    impl AsRef<str> for Tagged {
+        fn as_ref(&self) -> &str {
+            &self.text
+        }
+    }
+
+    #[test]
+    fn not_sync() {
+        let input: VecDeque<Tagged> = ["réservé", "résumé", "résidence"]
+            .into_iter()
+            .map(|s| Tagged {
+                text: s.to_string(),
+                _seen: RefCell::new(0),
+            })
+            .collect();
+        assert_eq!(input.common_prefix_seq_ref().unwrap(), "rés");
+        assert_eq!(input.common_suffix_seq_ref(), None);
+    }
+
+    #[test]
+    fn matches_parallel() {
+        let input: Vec<String> = (0..1 << 12).map(|i| format!("prefix_{i}_é")).collect();
+        assert_eq!(input.common_prefix_seq(), input.common_prefix());
+        assert_eq!(input.common_suffix_seq(), input.common_suffix());
+        for input in [vec![String::new()], vec![String::new(), String::new()]] {
+            assert_eq!(input.common_prefix_seq(), input.common_prefix());
+            assert_eq!(input.common_suffix_seq_ref(), input.common_suffix_ref());
+        }
+    }
+}
@@ -0,0 +1,87 @@
+/*!
+Contains the [`CommonBytes`] trait, which allows finding the longest common prefix/suffix
+of collections of [`Bytes`] without copying the result.
+
+Collections of [`Bytes`] already work with [`CommonRaw<u8>`](crate::CommonRaw), but the owned
+results of that trait are freshly allocated `Vec<u8>`s. Since [`Bytes`] can be cheaply sliced,
+this trait instead returns a [`Bytes`] sharing the buffer of the element the result came from.
+*/
+
+use crate::finder::*;
+use crate::reduce_by;
+use bytes::Bytes;
+use rayon::prelude::*;
+
+/// Trait for finding the longest common prefix/suffix of any 2D collection
+/// of [`Bytes`], returning zero-copy slices of the original buffers.
+pub trait CommonBytes {
+    /// Returns the longest common prefix of all [`Bytes`], sharing the
+    /// underlying buffer of one of them.
+    ///
+    /// Returns `None` when there is no common prefix.
+    fn common_prefix_bytes_shared(&self) -> Option<Bytes>;
+
+    /// Returns the longest common suffix of all [`Bytes`], sharing the
+    /// underlying buffer of one of them.
+    ///
+    /// Returns `None` when there is no common suffix.
+    fn common_suffix_bytes_shared(&self) -> Option<Bytes>;
+}
+
+impl<C: ?Sized> CommonBytes for C
+where
+    for<'a> &'a C: IntoParallelIterator<Item = &'a Bytes>,
+{
+    fn common_prefix_bytes_shared(&self) -> Option<Bytes> {
+        let iter = self.into_par_iter().map(|b| (b, b.as_ref()));
+        let (owner, prefix) = reduce_by(iter, |(owner, a), (_, b)| {
+            Some((owner, BytePrefix::common(a, b)?))
+        })?;
+        Some(owner.slice_ref(prefix))
+    }
+
+    fn common_suffix_bytes_shared(&self) -> Option<Bytes> {
+        let iter = self.into_par_iter().map(|b| (b, b.as_ref()));
+        let (owner, suffix) = reduce_by(iter, |(owner, a), (_, b)| {
+            Some((owner, ByteSuffix::common(a, b)?))
+        })?;
+        Some(owner.slice_ref(suffix))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CommonBytes;
+    use crate::CommonRaw;
+    use bytes::Bytes;
+
+    #[test]
+    fn shared() {
+        let input: Vec<Bytes> = (0..1 << 12)
+            .map(|i| Bytes::from(format!("payload:{i}:end")))
+            .collect();
+        assert_eq!(input.common_prefix_raw().unwrap(), b"payload:");
+        assert_eq!(input.common_suffix_raw().unwrap(), b":end");
+
+        let prefix = input.common_prefix_bytes_shared().unwrap();
+        assert_eq!(prefix, "payload:");
+        let owner = input
+            .iter()
+            .find(|b| b.as_ptr_range().contains(&prefix.as_ptr()))
+            .expect("result should share an element's buffer");
+        assert_eq!(owner.as_ptr(), prefix.as_ptr());
+        let suffix = input.common_suffix_bytes_shared().unwrap();
+        assert_eq!(suffix, ":end");
+        assert!(
+            input
+                .iter()
+                .any(|b| b.as_ptr_range().contains(&suffix.as_ptr()))
+        );
+
+        let input = vec![Bytes::from_static(b"abc"), Bytes::from_static(b"xyz")];
+        assert!(input.common_prefix_bytes_shared().is_none());
+        assert!(input.common_suffix_bytes_shared().is_none());
+        let input: Vec<Bytes> = Vec::new();
+        assert!(input.common_prefix_bytes_shared().is_none());
+    }
+}
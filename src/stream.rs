@@ -0,0 +1,104 @@
+/*!
+Contains [`common_prefix_stream`] and [`common_suffix_stream`], which find common
+prefixes/suffixes of strings arriving through an asynchronous [`Stream`].
+
+Items of a stream only become available one after another, so there's nothing to
+parallelize: the result is kept as a single running [`String`], shrunk by every item
+as it arrives. Items are awaited one at a time and never buffered, so the stream is
+only polled as fast as it's consumed, and no executor-specific APIs are needed.
+*/
+
+use crate::finder::*;
+use crate::keep_common;
+use futures_core::Stream;
+use std::future::poll_fn;
+use std::pin::{Pin, pin};
+
+/// Returns the longest common prefix of all strings yielded by `stream`.
+///
+/// The stream stops being polled as soon as nothing is left in common,
+/// so any remaining items are never consumed.
+///
+/// Returns `None` when there is no common prefix.
+pub async fn common_prefix_stream<S, T>(stream: S) -> Option<String>
+where
+    S: Stream<Item = T>,
+    T: AsRef<str>,
+{
+    fold_stream::<StringPrefix, _, _>(stream).await
+}
+
+/// Returns the longest common suffix of all strings yielded by `stream`.
+///
+/// The stream stops being polled as soon as nothing is left in common,
+/// so any remaining items are never consumed.
+///
+/// Returns `None` when there is no common suffix.
+pub async fn common_suffix_stream<S, T>(stream: S) -> Option<String>
+where
+    S: Stream<Item = T>,
+    T: AsRef<str>,
+{
+    fold_stream::<StringSuffix, _, _>(stream).await
+}
+
+async fn fold_stream<F, S, T>(stream: S) -> Option<String>
+where
+    F: Finder<str>,
+    S: Stream<Item = T>,
+    T: AsRef<str>,
+{
+    let mut stream = pin!(stream);
+    let mut common = next(stream.as_mut()).await?.as_ref().to_string();
+    if common.is_empty() {
+        return None;
+    }
+    while let Some(item) = next(stream.as_mut()).await {
+        common = keep_common::<F>(common, item.as_ref())?;
+    }
+    Some(common)
+}
+
+/// Awaits the next item of `stream`, like `StreamExt::next` does.
+async fn next<S: Stream>(mut stream: Pin<&mut S>) -> Option<S::Item> {
+    poll_fn(|cx| stream.as_mut().poll_next(cx)).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{common_prefix_stream, common_suffix_stream};
+    use futures::executor::block_on;
+    use futures::stream::{self, StreamExt};
+
+    #[test]
+    fn stream() {
+        let items = [
+            "log: started [ok]",
+            "log: running [ok]",
+            "log: stopped [ok]",
+        ];
+        let prefix = block_on(common_prefix_stream(stream::iter(items)));
+        let suffix = block_on(common_suffix_stream(stream::iter(items)));
+        assert_eq!(prefix.unwrap(), "log: ");
+        assert_eq!(suffix.unwrap(), " [ok]");
+        let owned = stream::iter(items.map(String::from));
+        assert_eq!(block_on(common_prefix_stream(owned)).unwrap(), "log: ");
+        let empty = stream::empty::<String>();
+        assert_eq!(block_on(common_prefix_stream(empty)), None);
+    }
+
+    #[test]
+    fn stops_early() {
+        let mut polled = 0;
+        let items = stream::iter(["abc", "abd", "xyz", "abe", "abf"]).inspect(|_| polled += 1);
+        assert_eq!(block_on(common_prefix_stream(items)), None);
+        assert_eq!(polled, 3);
+
+        // An empty first item has nothing in common with anything.
+        polled = 0;
+        let items = stream::iter(["", "abc"]).inspect(|_| polled += 1);
+        assert_eq!(block_on(common_suffix_stream(items)), None);
+        assert_eq!(polled, 1);
+        assert_eq!(block_on(common_prefix_stream(stream::iter([""]))), None);
+    }
+}
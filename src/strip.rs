@@ -0,0 +1,160 @@
+/*!
+Contains the [`CommonStrMut`] trait, which builds on top of [`CommonStr`] to remove
+common prefixes/suffixes from every string of a collection in place.
+*/
+
+use crate::CommonStr;
+use rayon::prelude::*;
+
+/// Trait for removing the longest common prefix/suffix from every [`String`]
+/// in any 2D collection which can be iterated over mutably.
+///
+/// Both finding what to remove and removing it from every string run in parallel.
+/// Removing a suffix just shortens each string, while removing a prefix has to
+/// move what remains of it to the front.
+pub trait CommonStrMut: CommonStr {
+    /// Removes the longest common prefix from every string in the collection,
+    /// returning the prefix which was removed.
+    ///
+    /// Strings consisting of only the prefix are left empty, and strings which
+    /// only differed in their prefix are left identical.
+    ///
+    /// Returns `None`, leaving the collection untouched, when there is no common prefix.
+    fn strip_common_prefix(&mut self) -> Option<String>;
+
+    /// Removes the longest common suffix from every string in the collection,
+    /// returning the suffix which was removed.
+    ///
+    /// Strings consisting of only the suffix are left empty, and strings which
+    /// only differed in their suffix are left identical.
+    ///
+    /// Returns `None`, leaving the collection untouched, when there is no common suffix.
+    fn strip_common_suffix(&mut self) -> Option<String>;
+
+    /// Removes the longest common prefix from every string in the collection,
+    /// returning the prefix which was removed, unless that would leave any string empty.
+    ///
+    /// This is all or nothing: when any string consists of only the prefix, nothing
+    /// is removed from any of them, so stripping never produces empty strings. That's
+    /// checked before anything gets modified, at the cost of one extra (parallel) pass
+    /// comparing the length of every string to the length of the prefix.
+    ///
+    /// Returns `None`, leaving the collection untouched, when there is no common
+    /// prefix or it makes up an entire string.
+    fn take_common_prefix(&mut self) -> Option<String>;
+}
+
+impl<C: ?Sized> CommonStrMut for C
+where
+    C: CommonStr,
+    for<'a> &'a mut C: IntoParallelIterator<Item = &'a mut String>,
+{
+    fn strip_common_prefix(&mut self) -> Option<String> {
+        let prefix = self.common_prefix()?;
+        let len = prefix.len();
+        self.into_par_iter().for_each(|s| {
+            s.drain(..len);
+        });
+        Some(prefix)
+    }
+
+    fn take_common_prefix(&mut self) -> Option<String> {
+        let prefix = self.common_prefix()?;
+        let len = prefix.len();
+        if self.into_par_iter().any(|s| s.len() == len) {
+            return None;
+        }
+        self.into_par_iter().for_each(|s| {
+            s.drain(..len);
+        });
+        Some(prefix)
+    }
+
+    fn strip_common_suffix(&mut self) -> Option<String> {
+        let suffix = self.common_suffix()?;
+        let len = suffix.len();
+        // The suffix starts with a whole char in every string,
+        // so this always lies on a char boundary.
+        self.into_par_iter().for_each(|s| s.truncate(s.len() - len));
+        Some(suffix)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CommonStrMut;
+
+    #[test]
+    fn strip_prefix() {
+        let mut input = vec!["foobar".to_string(), "fooqux".into(), "foo".into()];
+        assert_eq!(input.strip_common_prefix().unwrap(), "foo");
+        assert_eq!(input, ["bar", "qux", ""]);
+        // Nothing is left in common, since one of the strings is now empty.
+        assert_eq!(input.strip_common_prefix(), None);
+        assert_eq!(input, ["bar", "qux", ""]);
+
+        let mut input = ["a_same".to_string(), "b_same".into(), "ab_same".into()];
+        assert_eq!(input.strip_common_prefix(), None);
+        assert_eq!(input, ["a_same", "b_same", "ab_same"]);
+
+        let mut input = vec!["réservé_x".to_string(), "réservé_x".into(), "résumé".into()];
+        assert_eq!(input.strip_common_prefix().unwrap(), "rés");
+        assert_eq!(input, ["ervé_x", "ervé_x", "umé"]);
+
+        let mut input = vec!["identical".to_string(); 1 << 12];
+        assert_eq!(input.strip_common_prefix().unwrap(), "identical");
+        assert!(input.iter().all(String::is_empty));
+
+        let mut input: Vec<String> = Vec::new();
+        assert_eq!(input.strip_common_prefix(), None);
+    }
+
+    #[test]
+    fn take_prefix() {
+        let mut input = vec!["foobar".to_string(), "fooqux".into(), "foo".into()];
+        assert_eq!(input.take_common_prefix(), None);
+        assert_eq!(input, ["foobar", "fooqux", "foo"]);
+        input.pop();
+        assert_eq!(input.take_common_prefix().unwrap(), "foo");
+        assert_eq!(input, ["bar", "qux"]);
+        assert_eq!(input.take_common_prefix(), None);
+
+        let mut input = vec!["identical".to_string(); 1 << 12];
+        assert_eq!(input.take_common_prefix(), None);
+        assert!(input.iter().all(|s| s == "identical"));
+        // A single string is all prefix.
+        let mut input = vec!["alone".to_string()];
+        assert_eq!(input.take_common_prefix(), None);
+
+        let mut input: Vec<String> = (0..1 << 12).map(|i| format!("prefix_{i}")).collect();
+        input.push("prefix_".into());
+        assert_eq!(input.take_common_prefix(), None);
+        input.pop();
+        assert_eq!(input.take_common_prefix().unwrap(), "prefix_");
+        assert!(input.iter().all(|s| !s.is_empty()));
+    }
+
+    #[test]
+    fn strip_suffix() {
+        let mut input = vec![
+            "GET / [OK]\n".to_string(),
+            "PUT /x [OK]\n".into(),
+            " [OK]\n".into(),
+        ];
+        assert_eq!(input.strip_common_suffix().unwrap(), " [OK]\n");
+        assert_eq!(input, ["GET /", "PUT /x", ""]);
+        assert_eq!(input.strip_common_suffix(), None);
+        assert_eq!(input, ["GET /", "PUT /x", ""]);
+
+        let mut input = vec!["x_ésumé".to_string(), "y_ésumé".into(), "résumé".into()];
+        assert_eq!(input.strip_common_suffix().unwrap(), "ésumé");
+        assert_eq!(input, ["x_", "y_", "r"]);
+
+        let mut input = vec!["identical".to_string(); 1 << 12];
+        assert_eq!(input.strip_common_suffix().unwrap(), "identical");
+        assert!(input.iter().all(String::is_empty));
+
+        let mut input: Vec<String> = Vec::new();
+        assert_eq!(input.strip_common_suffix(), None);
+    }
+}
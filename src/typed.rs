@@ -0,0 +1,56 @@
+/*!
+Contains [`PrefixRef`], a typed alternative to the plain `&str` results of
+[`CommonStr`](crate::CommonStr), which also records which element the result borrows from.
+*/
+
+use crate::finder::nonzero_len;
+use std::fmt;
+use std::num::NonZeroUsize;
+
+/// Common prefix borrowed from one specific element of a collection,
+/// along with the index of that element.
+///
+/// Deliberately doesn't implement `Deref<Target = str>`, so the provenance
+/// can't be silently lost. Use [`PrefixRef::as_str`] to get at the string.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct PrefixRef<'a> {
+    prefix: &'a str,
+    index: usize,
+}
+
+impl<'a> PrefixRef<'a> {
+    pub(crate) fn new(prefix: &'a str, index: usize) -> Self {
+        debug_assert!(!prefix.is_empty());
+        Self { prefix, index }
+    }
+
+    /// Returns the prefix itself.
+    #[inline]
+    pub fn as_str(&self) -> &'a str {
+        self.prefix
+    }
+
+    /// Returns the length of the prefix, in bytes.
+    #[inline]
+    pub fn len(&self) -> NonZeroUsize {
+        nonzero_len(self.prefix.len())
+    }
+
+    /// Returns the index of the element the prefix borrows from.
+    #[inline]
+    pub fn source_index(&self) -> usize {
+        self.index
+    }
+}
+
+impl AsRef<str> for PrefixRef<'_> {
+    fn as_ref(&self) -> &str {
+        self.prefix
+    }
+}
+
+impl fmt::Display for PrefixRef<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.prefix)
+    }
+}
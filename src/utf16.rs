@@ -0,0 +1,87 @@
+/*!
+Contains the [`CommonUtf16`] trait, for finding common prefixes/suffixes of UTF-16 text.
+
+[`CommonRaw`](crate::CommonRaw) already works on collections of `u16` slices, but compares
+them one element at a time and knows nothing about surrogate pairs, so a result can end
+with half of one. This trait compares code units in chunks like the string finders do with
+bytes, and makes sure results never split a surrogate pair.
+*/
+
+use crate::finder::*;
+use crate::reduce_common;
+use rayon::prelude::*;
+
+/// Trait for finding the longest common prefix/suffix of UTF-16 encoded text,
+/// for any 2D collection of `u16` slices.
+pub trait CommonUtf16 {
+    /// Returns a reference to the longest common prefix of all referenced text,
+    /// never ending between the two halves of a surrogate pair.
+    ///
+    /// Returns `None` when there is no common prefix.
+    fn common_prefix_utf16(&self) -> Option<&[u16]>;
+
+    /// Returns a reference to the longest common suffix of all referenced text,
+    /// never starting between the two halves of a surrogate pair.
+    ///
+    /// Returns `None` when there is no common suffix.
+    fn common_suffix_utf16(&self) -> Option<&[u16]>;
+}
+
+impl<C: ?Sized, T> CommonUtf16 for C
+where
+    for<'a> &'a C: IntoParallelIterator<Item = &'a T>,
+    T: AsRef<[u16]> + Sync,
+{
+    fn common_prefix_utf16(&self) -> Option<&[u16]> {
+        let iter = self.into_par_iter().map(|s| s.as_ref());
+        reduce_common::<Utf16Prefix, _, _>(iter)
+    }
+
+    fn common_suffix_utf16(&self) -> Option<&[u16]> {
+        let iter = self.into_par_iter().map(|s| s.as_ref());
+        reduce_common::<Utf16Suffix, _, _>(iter)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CommonUtf16;
+    use crate::CommonStr;
+
+    fn utf16(s: &str) -> Vec<u16> {
+        s.encode_utf16().collect()
+    }
+
+    #[test]
+    fn surrogate_pairs() {
+        // Both emoji share their high surrogate, but not their low one.
+        let input = [utf16("smile_😀_a"), utf16("smile_😁_b")];
+        assert_eq!(input[0][6], input[1][6]);
+        assert_eq!(input.common_prefix_utf16().unwrap(), utf16("smile_"));
+        let input = [utf16("a_😀"), utf16("b_😀")];
+        assert_eq!(input.common_suffix_utf16().unwrap(), utf16("_😀"));
+        // Only the low surrogates match, which mustn't be split off their pairs.
+        let input = [utf16("x\u{1F600}"), utf16("x\u{1F200}")];
+        assert_eq!(input[0][2], input[1][2]);
+        assert_eq!(input.common_suffix_utf16(), None);
+        let input = [utf16("😀"), utf16("😁")];
+        assert_eq!(input.common_prefix_utf16(), None);
+        // One slice ends in the middle of the pair the other one completes.
+        let input = [utf16("ab😀")[..3].to_vec(), utf16("ab😀")];
+        assert_eq!(input.common_prefix_utf16().unwrap(), utf16("ab"));
+        let input: [Vec<u16>; 0] = [];
+        assert_eq!(input.common_prefix_utf16(), None);
+    }
+
+    #[test]
+    fn matches_str() {
+        let strings: Vec<String> = (0..1 << 12)
+            .map(|i| format!("ünïcödé_😀_{i}_wörds_🐀"))
+            .collect();
+        let input: Vec<Vec<u16>> = strings.iter().map(|s| utf16(s)).collect();
+        let prefix = String::from_utf16(input.common_prefix_utf16().unwrap()).unwrap();
+        let suffix = String::from_utf16(input.common_suffix_utf16().unwrap()).unwrap();
+        assert_eq!(Some(prefix), strings.common_prefix());
+        assert_eq!(Some(suffix), strings.common_suffix());
+    }
+}